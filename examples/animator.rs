@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use bevy_noti_box::{NotiAnimationTarget, NotiBoxBuilder, NotiBoxEvent, NotiBoxPluginAnyState, NotiBoxState};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(NotiBoxPluginAnyState::any())
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// Bounce in with an elastic curve, then shrink away.
+/// See the `tweening` example to play `bevy_tweening` tweens instead.
+fn bounce(state: NotiBoxState, progress: f32, target: &mut NotiAnimationTarget) {
+    let curve = match state {
+        NotiBoxState::Leaving => EaseFunction::QuadraticIn,
+        _ => EaseFunction::ElasticOut,
+    };
+    let eased = EasingCurve::new(0., 1., curve).sample_clamped(progress);
+    target.transform.scale = Vec2::splat(eased);
+    target.transform.translation = Val2::px(0., (1. - eased) * -40.);
+    target.alpha = progress;
+}
+
+fn setup(mut commands: Commands, mut event: MessageWriter<NotiBoxEvent>) {
+    commands.spawn(Camera2d);
+
+    NotiBoxBuilder::msg("Achievement unlocked!")
+        .show_for(3.)
+        .fade(1., 0.3)
+        .animator(bounce)
+        .send(&mut event);
+}
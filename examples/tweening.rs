@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_noti_box::{NotiAlphaLens, NotiBoxBuilder, NotiBoxEvent, NotiBoxPluginAnyState, NotiScaleLens, NotiTweens};
+use bevy_tweening::Tween;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(NotiBoxPluginAnyState::any())
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut event: MessageWriter<NotiBoxEvent>) {
+    commands.spawn(Camera2d);
+
+    NotiBoxBuilder::msg("Slide in from the left")
+        .show_for(3.)
+        .fade(0.5, 0.5)
+        .animator(NotiTweens::slide(Val2::px(-200., 0.), EaseFunction::CubicOut))
+        .send(&mut event);
+
+    // Bounce in, then shrink away twice as fast as it fades
+    let tweens = NotiTweens::new()
+        .with_entrance(Tween::new(
+            EaseFunction::ElasticOut,
+            Duration::from_secs(1),
+            NotiScaleLens {
+                start: Vec2::ZERO,
+                end: Vec2::ONE,
+            },
+        ))
+        .with_exit(Tween::new(
+            EaseFunction::QuadraticIn,
+            Duration::from_millis(500),
+            NotiScaleLens {
+                start: Vec2::ONE,
+                end: Vec2::ZERO,
+            },
+        ))
+        .with_exit(Tween::new(
+            EaseFunction::Linear,
+            Duration::from_secs(1),
+            NotiAlphaLens { start: 1., end: 0. },
+        ));
+    NotiBoxBuilder::msg("Achievement unlocked!")
+        .show_for(3.)
+        .fade(1., 0.6)
+        .animator(tweens)
+        .send(&mut event);
+}
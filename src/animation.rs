@@ -0,0 +1,98 @@
+use crate::{NotiBoxState, NotiPosition};
+use bevy::prelude::*;
+
+/// How a notification enters and leaves the screen
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotiAnimation {
+    /// Slide in from the screen edge nearest to its position while fading
+    SlideFromEdge,
+    /// Slide down from the top of its position while fading
+    SlideFromTop,
+    #[default]
+    Fade,
+    /// Grow from 80% to full size with a slight overshoot while fading
+    Pop,
+    /// Appear and disappear right away
+    None,
+    /// Fade in in place, then float upward while fading out
+    Rise,
+}
+
+impl NotiAnimation {
+    /// Opacity at `progress`, which goes from 0 (hidden) to 1 (fully shown)
+    pub(crate) fn alpha(&self, progress: f32) -> f32 {
+        match self {
+            NotiAnimation::None => 1.,
+            _ => progress,
+        }
+    }
+
+    /// Offset from the resting place at `progress`
+    pub(crate) fn translation(&self, pos: NotiPosition, progress: f32) -> Val2 {
+        let distance = (1. - progress) * SLIDE_DISTANCE;
+        match self {
+            NotiAnimation::SlideFromEdge => match pos {
+                NotiPosition::TopLeft | NotiPosition::MidLeft | NotiPosition::BotLeft => Val2::percent(-distance, 0.),
+                NotiPosition::TopRight | NotiPosition::MidRight | NotiPosition::BotRight => Val2::percent(distance, 0.),
+                NotiPosition::TopMid | NotiPosition::Center | NotiPosition::Custom { .. } => {
+                    Val2::percent(0., -distance)
+                }
+                NotiPosition::BotMid => Val2::percent(0., distance),
+            },
+            NotiAnimation::SlideFromTop => Val2::percent(0., -distance),
+            NotiAnimation::Rise => Val2::percent(0., -(1. - progress) * RISE_DISTANCE),
+            NotiAnimation::Fade | NotiAnimation::Pop | NotiAnimation::None => Val2::percent(0., 0.),
+        }
+    }
+
+    /// Scale at `progress`
+    pub(crate) fn scale(&self, progress: f32) -> Vec2 {
+        match self {
+            NotiAnimation::Pop => {
+                // Back ease-out, overshooting a bit before settling at 1
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.;
+                let t = progress - 1.;
+                let eased = 1. + C3 * t.powi(3) + C1 * t.powi(2);
+                Vec2::splat(POP_START_SCALE + (1. - POP_START_SCALE) * eased)
+            }
+            _ => Vec2::ONE,
+        }
+    }
+}
+
+const POP_START_SCALE: f32 = 0.8;
+
+/// How far a sliding notification starts from its resting place, in percent of its own size
+const SLIDE_DISTANCE: f32 = 110.;
+
+/// How far a rising notification floats up before it is gone, in percent of its own height
+const RISE_DISTANCE: f32 = 150.;
+
+/// Custom animation of a notification, see [`NotiBoxEvent::animator`](crate::NotiBoxEvent::animator).
+/// The timing still follows the fade-in, show time and fade-out of the notification.
+/// With the `tweening` feature, `NotiTweens` plays `bevy_tweening` tweens this way.
+pub trait NotiAnimator: Send + Sync + 'static {
+    /// Called every frame with `progress` going from 0 (hidden) to 1 (fully shown) while `state` is
+    /// [`NotiBoxState::Entering`], staying at 1 while it is shown, and going back to 0 while it is leaving
+    fn animate(&self, state: NotiBoxState, progress: f32, target: &mut NotiAnimationTarget);
+}
+
+impl<F> NotiAnimator for F
+where
+    F: Fn(NotiBoxState, f32, &mut NotiAnimationTarget) + Send + Sync + 'static,
+{
+    fn animate(&self, state: NotiBoxState, progress: f32, target: &mut NotiAnimationTarget) {
+        self(state, progress, target)
+    }
+}
+
+/// What a [`NotiAnimator`] changes on the notification
+pub struct NotiAnimationTarget<'a> {
+    pub node: &'a mut Node,
+    /// Not moved by swipes while a [`NotiAnimator`] is used
+    pub transform: &'a mut UiTransform,
+    /// Opacity of the notification and its content, `1.` unless changed
+    pub alpha: f32,
+}
@@ -0,0 +1,17 @@
+use crate::{NotiBoxEvent, NotiBoxSettings};
+use bevy::{asset::UntypedAssetLoadFailedEvent, prelude::*};
+
+/// Show an error notification for each asset failing to load, see [`NotiBoxSettings::asset_errors`]
+pub(crate) fn listen_asset_errors(
+    mut failed: MessageReader<UntypedAssetLoadFailedEvent>,
+    settings: Res<NotiBoxSettings>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+) {
+    if !settings.asset_errors {
+        failed.clear();
+        return;
+    }
+    for event in failed.read() {
+        notis.write(NotiBoxEvent::error(format!("Failed to load {}", event.path)));
+    }
+}
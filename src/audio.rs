@@ -0,0 +1,19 @@
+use bevy::{
+    ecs::{lifecycle::HookContext, world::DeferredWorld},
+    prelude::*,
+};
+
+/// Sound played when the notification it is attached to goes away, whatever the reason
+#[derive(Component)]
+#[component(on_remove = play_dismiss_sound)]
+pub(crate) struct NotiDismissSound(pub(crate) Handle<AudioSource>);
+
+fn play_dismiss_sound(mut world: DeferredWorld, ctx: HookContext) {
+    if let Some(sound) = world.get::<NotiDismissSound>(ctx.entity).map(|s| s.0.clone()) {
+        play_sound(&mut world.commands(), sound);
+    }
+}
+
+pub(crate) fn play_sound(commands: &mut Commands, sound: Handle<AudioSource>) {
+    commands.spawn((AudioPlayer::new(sound), PlaybackSettings::DESPAWN));
+}
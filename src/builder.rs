@@ -0,0 +1,313 @@
+use crate::{
+    NotiAction, NotiAnimation, NotiAnimator, NotiBoxEvent, NotiBoxId, NotiLevel, NotiPosition, NotiPriority,
+    NotiTextSpan,
+};
+use bevy::{camera::visibility::RenderLayers, prelude::*, text::LineHeight};
+use std::sync::Arc;
+
+/// Build a [`NotiBoxEvent`] step by step.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_noti_box::*;
+/// fn save(mut writer: MessageWriter<NotiBoxEvent>) {
+///     NotiBoxBuilder::msg("Saved").pos(NotiPosition::TopLeft).show_for(3.).send(&mut writer);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct NotiBoxBuilder(NotiBoxEvent);
+
+impl NotiBoxBuilder {
+    pub fn msg(msg: impl Into<String>) -> Self {
+        Self(NotiBoxEvent {
+            msg: msg.into(),
+            ..default()
+        })
+    }
+
+    /// Start from a notification styled after `level`
+    pub fn level(level: NotiLevel, msg: impl Into<String>) -> Self {
+        Self(NotiBoxEvent::from_level(level, msg))
+    }
+
+    pub fn id(mut self, id: NotiBoxId) -> Self {
+        self.0.id = Some(id);
+        self
+    }
+
+    pub fn span(mut self, span: NotiTextSpan) -> Self {
+        self.0.spans.push(span);
+        self
+    }
+
+    pub fn font(mut self, font: TextFont) -> Self {
+        self.0.font = font;
+        self
+    }
+
+    pub fn font_path(mut self, path: impl Into<String>) -> Self {
+        self.0.font_path = Some(path.into());
+        self
+    }
+
+    pub fn text_color(mut self, color: impl Into<Color>) -> Self {
+        self.0.text_color = color.into();
+        self
+    }
+
+    pub fn pos(mut self, pos: NotiPosition) -> Self {
+        self.0.pos = pos;
+        self
+    }
+
+    /// Show time in seconds. See [`NotiBoxEvent::show_time`].
+    pub fn show_for(mut self, seconds: f32) -> Self {
+        self.0.show_time = seconds;
+        self
+    }
+
+    /// See [`NotiBoxEvent::delay`]
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.0.delay = seconds;
+        self
+    }
+
+    /// Stay until clicked or dismissed
+    pub fn sticky(self) -> Self {
+        self.show_for(0.)
+    }
+
+    /// Background color
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.0.background_color = BackgroundColor(color.into());
+        self
+    }
+
+    /// See [`NotiBoxEvent::background_image`]
+    pub fn background_image(mut self, image: ImageNode) -> Self {
+        self.0.background_image = Some(image);
+        self
+    }
+
+    /// See [`NotiBoxEvent::with_material`]
+    #[cfg(feature = "material")]
+    pub fn material<M: bevy::ui_render::prelude::UiMaterial>(self, material: Handle<M>) -> Self {
+        Self(self.0.with_material(material))
+    }
+
+    pub fn border_color(mut self, color: impl Into<Color>) -> Self {
+        self.0.border_color = Some(color.into());
+        self
+    }
+
+    pub fn border(mut self, border: UiRect) -> Self {
+        self.0.border = Some(border);
+        self
+    }
+
+    pub fn border_radius(mut self, radius: BorderRadius) -> Self {
+        self.0.border_radius = Some(radius);
+        self
+    }
+
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.0.z_index = Some(z_index);
+        self
+    }
+
+    pub fn priority(mut self, priority: NotiPriority) -> Self {
+        self.0.priority = priority;
+        self
+    }
+
+    pub fn icon(mut self, icon: Handle<Image>) -> Self {
+        self.0.icon = Some(icon);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.0.tag = Some(tag.into());
+        self
+    }
+
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.0.channel = Some(channel.into());
+        self
+    }
+
+    pub fn close_button(mut self) -> Self {
+        self.0.close_button = true;
+        self
+    }
+
+    /// See [`NotiBoxEvent::copy_button`]
+    pub fn copy_button(mut self) -> Self {
+        self.0.copy_button = true;
+        self
+    }
+
+    pub fn action(mut self, action: NotiAction) -> Self {
+        self.0.actions.push(action);
+        self
+    }
+
+    pub fn progress_bar(mut self) -> Self {
+        self.0.progress_bar = true;
+        self
+    }
+
+    /// See [`NotiBoxEvent::progress`]
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.0.progress = Some(progress);
+        self
+    }
+
+    /// See [`NotiBoxEvent::spinner`]
+    pub fn spinner(mut self) -> Self {
+        self.0.spinner = true;
+        self
+    }
+
+    /// See [`NotiBoxEvent::modal`]
+    pub fn modal(mut self) -> Self {
+        self.0.modal = true;
+        self
+    }
+
+    /// See [`NotiBoxEvent::interactive`]
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.0.interactive = interactive;
+        self
+    }
+
+    pub fn entrance(mut self, entrance: NotiAnimation) -> Self {
+        self.0.entrance = entrance;
+        self
+    }
+
+    pub fn exit(mut self, exit: NotiAnimation) -> Self {
+        self.0.exit = Some(exit);
+        self
+    }
+
+    pub fn easing(mut self, easing: EaseFunction) -> Self {
+        self.0.easing = easing;
+        self
+    }
+
+    /// See [`NotiBoxEvent::animator`]
+    pub fn animator(mut self, animator: impl NotiAnimator) -> Self {
+        self.0.animator = Some(Arc::new(animator));
+        self
+    }
+
+    /// Entrance and exit durations in seconds
+    pub fn fade(mut self, fade_in: f32, fade_out: f32) -> Self {
+        self.0.fade_in = Some(fade_in);
+        self.0.fade_out = Some(fade_out);
+        self
+    }
+
+    pub fn parent(mut self, parent: Entity) -> Self {
+        self.0.parent = Some(parent);
+        self
+    }
+
+    pub fn camera(mut self, camera: Entity) -> Self {
+        self.0.camera = Some(camera);
+        self
+    }
+
+    pub fn render_layers(mut self, layers: RenderLayers) -> Self {
+        self.0.render_layers = Some(layers);
+        self
+    }
+
+    pub fn size(mut self, width: Val, height: Val) -> Self {
+        self.0.width = width;
+        self.0.height = height;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: Val) -> Self {
+        self.0.max_width = Some(max_width);
+        self
+    }
+
+    pub fn min_width(mut self, min_width: Val) -> Self {
+        self.0.min_width = Some(min_width);
+        self
+    }
+
+    pub fn min_height(mut self, min_height: Val) -> Self {
+        self.0.min_height = Some(min_height);
+        self
+    }
+
+    pub fn max_height(mut self, max_height: Val) -> Self {
+        self.0.max_height = Some(max_height);
+        self
+    }
+
+    pub fn linebreak(mut self, linebreak: LineBreak) -> Self {
+        self.0.linebreak = Some(linebreak);
+        self
+    }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.0.justify = Some(justify);
+        self
+    }
+
+    pub fn line_height(mut self, line_height: LineHeight) -> Self {
+        self.0.line_height = Some(line_height);
+        self
+    }
+
+    /// Horizontal and vertical alignment of the content
+    pub fn content_align(mut self, align_items: AlignItems, justify_content: JustifyContent) -> Self {
+        self.0.align_items = Some(align_items);
+        self.0.justify_content = Some(justify_content);
+        self
+    }
+
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.0.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Type the message at `chars_per_second`
+    pub fn typewriter(mut self, chars_per_second: f32) -> Self {
+        self.0.typewriter = chars_per_second;
+        self
+    }
+
+    /// Scroll the message on a single line at `speed` logical pixels per second if it does not fit
+    pub fn marquee(mut self, speed: f32) -> Self {
+        self.0.marquee = speed;
+        self
+    }
+
+    /// See [`NotiBoxEvent::insert`]
+    pub fn insert(self, bundle: impl Bundle + Clone) -> Self {
+        Self(self.0.insert(bundle))
+    }
+
+    pub fn build(self) -> NotiBoxEvent {
+        self.0
+    }
+
+    /// Send the notification and return its id
+    pub fn send(self, writer: &mut MessageWriter<NotiBoxEvent>) -> NotiBoxId {
+        let mut noti = self.0;
+        let id = *noti.id.get_or_insert_with(NotiBoxId::unique);
+        writer.write(noti);
+        id
+    }
+}
+
+impl From<NotiBoxBuilder> for NotiBoxEvent {
+    fn from(builder: NotiBoxBuilder) -> Self {
+        builder.0
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{NotiBoxEvent, NotiBoxTheme, NotiOverflow, NotiPosition};
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Defaults shared by the notifications of a channel, e.g. "combat" at the bottom left and "system" at the top right
+#[derive(Clone, Default, Reflect)]
+pub struct NotiChannel {
+    /// Position of the notifications left at the default one
+    pub pos: Option<NotiPosition>,
+    /// Used instead of the [`NotiBoxTheme`] resource for the notifications of the channel
+    pub theme: Option<NotiBoxTheme>,
+    /// How many notifications of the channel are shown at once. `0` for no limit.
+    pub max_visible: usize,
+    /// What happens to a new notification beyond `max_visible`, by default the oldest one goes away
+    pub overflow: NotiOverflow,
+    /// Drop the notifications of the channel, see [`NotiChannels::mute`]
+    pub muted: bool,
+    /// Still record the notifications of the channel in [`NotiHistory`](crate::NotiHistory) while it is muted
+    pub record_muted: bool,
+}
+
+impl NotiChannel {
+    pub fn with_pos(mut self, pos: NotiPosition) -> Self {
+        self.pos = Some(pos);
+        self
+    }
+
+    pub fn with_theme(mut self, theme: NotiBoxTheme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn with_max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible;
+        self
+    }
+
+    pub fn with_overflow(mut self, overflow: NotiOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_muted(mut self, muted: bool) -> Self {
+        self.muted = muted;
+        self
+    }
+
+    pub fn with_record_muted(mut self, record_muted: bool) -> Self {
+        self.record_muted = record_muted;
+        self
+    }
+}
+
+/// Channels [`NotiBoxEvent::channel`] refers to. Notifications of an unknown channel are shown as usual.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct NotiChannels(HashMap<String, NotiChannel>);
+
+impl NotiChannels {
+    pub fn insert(&mut self, name: impl Into<String>, channel: NotiChannel) -> &mut Self {
+        self.0.insert(name.into(), channel);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NotiChannel> {
+        self.0.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NotiChannel> {
+        self.0.get_mut(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<NotiChannel> {
+        self.0.remove(name)
+    }
+
+    /// Stop showing the notifications of a channel, e.g. when a player turns off item pickup toasts.
+    /// The channel is added if unknown.
+    pub fn mute(&mut self, name: impl Into<String>) {
+        self.0.entry(name.into()).or_default().muted = true;
+    }
+
+    pub fn unmute(&mut self, name: &str) {
+        if let Some(channel) = self.0.get_mut(name) {
+            channel.muted = false;
+        }
+    }
+
+    pub fn is_muted(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|channel| channel.muted)
+    }
+
+    /// Channel `noti` is sent to
+    pub(crate) fn of(&self, noti: &NotiBoxEvent) -> Option<&NotiChannel> {
+        noti.channel.as_deref().and_then(|name| self.get(name))
+    }
+}
@@ -0,0 +1,22 @@
+use crate::NotiCopyRequested;
+use arboard::Clipboard;
+use bevy::prelude::*;
+
+/// Put the message of a notification in the clipboard when its copy button is clicked.
+/// The clipboard is kept open, some platforms empty it once closed.
+pub(crate) fn copy_to_clipboard(mut event: MessageReader<NotiCopyRequested>, mut clipboard: Local<Option<Clipboard>>) {
+    for copy in event.read() {
+        if clipboard.is_none() {
+            match Clipboard::new() {
+                Ok(opened) => *clipboard = Some(opened),
+                Err(err) => {
+                    warn!("Failed to open the clipboard: {err}");
+                    continue;
+                }
+            }
+        }
+        if let Some(Err(err)) = clipboard.as_mut().map(|clipboard| clipboard.set_text(&copy.text)) {
+            warn!("Failed to copy a notification to the clipboard: {err}");
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{NotiAction, NotiActionTriggered, NotiBoxDismissed, NotiBoxDropped, NotiBoxEvent, NotiBoxId, NotiPosition};
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Action payloads of the two buttons of a dialog
+const CONFIRM: &str = "confirm";
+const CANCEL: &str = "cancel";
+
+/// Ask a yes/no question, answered with [`NotiConfirmed`] or [`NotiCancelled`].
+/// Dismissing the dialog in any other way, e.g. with its close button, cancels it, like a dialog that is never shown,
+/// e.g. muted or merged into the same one already on screen.
+#[derive(Message, Clone)]
+pub struct NotiConfirmEvent {
+    pub msg: String,
+    pub confirm_label: String,
+    pub cancel_label: String,
+    /// Passed back with the answer, e.g. the save slot to delete
+    pub payload: String,
+    /// Look and position of the dialog. Its message, buttons and show time are replaced.
+    pub noti: NotiBoxEvent,
+}
+
+impl NotiConfirmEvent {
+    /// Modal "Yes"/"No" dialog at the center of the screen
+    pub fn new(msg: impl Into<String>, payload: impl Into<String>) -> Self {
+        Self {
+            msg: msg.into(),
+            confirm_label: "Yes".to_string(),
+            cancel_label: "No".to_string(),
+            payload: payload.into(),
+            noti: NotiBoxEvent {
+                pos: NotiPosition::Center,
+                modal: true,
+                ..default()
+            },
+        }
+    }
+}
+
+/// Sent when the confirm button of a dialog is clicked
+#[derive(Message, Clone, Debug)]
+pub struct NotiConfirmed {
+    pub id: NotiBoxId,
+    pub payload: String,
+}
+
+/// Sent when a dialog is dismissed without clicking its confirm button, or is never shown
+#[derive(Message, Clone, Debug)]
+pub struct NotiCancelled {
+    pub id: NotiBoxId,
+    pub payload: String,
+}
+
+/// Payloads of the dialogs not answered yet
+#[derive(Resource, Default)]
+pub(crate) struct PendingConfirms(HashMap<NotiBoxId, String>);
+
+pub(crate) fn listen_confirm_event(
+    mut event: MessageReader<NotiConfirmEvent>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+    mut pending: ResMut<PendingConfirms>,
+) {
+    for confirm in event.read() {
+        let id = confirm.noti.id.unwrap_or_else(NotiBoxId::unique);
+        pending.0.insert(id, confirm.payload.clone());
+        notis.write(NotiBoxEvent {
+            id: Some(id),
+            msg: confirm.msg.clone(),
+            show_time: 0.,
+            actions: vec![
+                NotiAction::new(confirm.confirm_label.clone(), CONFIRM),
+                NotiAction::new(confirm.cancel_label.clone(), CANCEL),
+            ],
+            // Clicking the body must not answer the dialog
+            close_button: true,
+            interactive: true,
+            ..confirm.noti.clone()
+        });
+    }
+}
+
+pub(crate) fn listen_confirm_answer(
+    mut triggered: MessageReader<NotiActionTriggered>,
+    mut dismissed: MessageReader<NotiBoxDismissed>,
+    mut dropped: MessageReader<NotiBoxDropped>,
+    mut pending: ResMut<PendingConfirms>,
+    mut confirmed: MessageWriter<NotiConfirmed>,
+    mut cancelled: MessageWriter<NotiCancelled>,
+) {
+    for action in triggered.read() {
+        let Some(payload) = pending.0.remove(&action.id) else {
+            continue;
+        };
+        if action.action.payload == CONFIRM {
+            confirmed.write(NotiConfirmed { id: action.id, payload });
+        } else {
+            cancelled.write(NotiCancelled { id: action.id, payload });
+        }
+    }
+    let gone = dismissed
+        .read()
+        .map(|dismissed| dismissed.id)
+        .chain(dropped.read().map(|dropped| dropped.id));
+    for id in gone {
+        if let Some(payload) = pending.0.remove(&id) {
+            cancelled.write(NotiCancelled { id, payload });
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::NotiBoxEvent;
+use bevy::{
+    diagnostic::{DiagnosticPath, DiagnosticsStore},
+    prelude::*,
+};
+
+/// Notification sent once a diagnostic stayed past a threshold for some time, e.g. the FPS under 30.
+/// It is sent again only after the diagnostic went back.
+#[derive(Clone)]
+pub struct NotiDiagnosticWatch {
+    pub path: DiagnosticPath,
+    pub threshold: f64,
+    /// Watch for the smoothed value going above `threshold` instead of below
+    pub above: bool,
+    /// Seconds the value must stay past `threshold`
+    pub duration: f32,
+    pub noti: NotiBoxEvent,
+    /// Seconds the value has been past `threshold`
+    past_for: f32,
+    /// Whether `noti` was sent since the value went past `threshold`
+    sent: bool,
+}
+
+impl NotiDiagnosticWatch {
+    pub fn below(path: DiagnosticPath, threshold: f64, duration: f32, noti: impl Into<NotiBoxEvent>) -> Self {
+        Self {
+            path,
+            threshold,
+            above: false,
+            duration,
+            noti: noti.into(),
+            past_for: 0.,
+            sent: false,
+        }
+    }
+
+    pub fn above(path: DiagnosticPath, threshold: f64, duration: f32, noti: impl Into<NotiBoxEvent>) -> Self {
+        Self {
+            above: true,
+            ..Self::below(path, threshold, duration, noti)
+        }
+    }
+}
+
+/// Diagnostics watched for notifications, read from [`DiagnosticsStore`]
+///
+/// ```no_run
+/// # use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
+/// # use bevy_noti_box::*;
+/// fn setup(mut diagnostics: ResMut<NotiDiagnostics>) {
+///     diagnostics.watch(NotiDiagnosticWatch::below(
+///         FrameTimeDiagnosticsPlugin::FPS,
+///         30.,
+///         5.,
+///         NotiBoxEvent::warning("Performance degraded"),
+///     ));
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct NotiDiagnostics(Vec<NotiDiagnosticWatch>);
+
+impl NotiDiagnostics {
+    pub fn watch(&mut self, watch: NotiDiagnosticWatch) -> &mut Self {
+        self.0.push(watch);
+        self
+    }
+
+    /// Stop watching every diagnostic at `path`
+    pub fn unwatch(&mut self, path: &DiagnosticPath) {
+        self.0.retain(|watch| watch.path != *path);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+pub(crate) fn watch_diagnostics(
+    store: Option<Res<DiagnosticsStore>>,
+    mut diagnostics: ResMut<NotiDiagnostics>,
+    // Frame rate is about real time, whether the game is paused or not
+    time: Res<Time<Real>>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+) {
+    let Some(store) = store else {
+        return;
+    };
+    for watch in diagnostics.0.iter_mut() {
+        let Some(value) = store.get(&watch.path).and_then(|diagnostic| diagnostic.smoothed()) else {
+            continue;
+        };
+        let past = if watch.above {
+            value > watch.threshold
+        } else {
+            value < watch.threshold
+        };
+        if !past {
+            watch.past_for = 0.;
+            watch.sent = false;
+            continue;
+        }
+        watch.past_for += time.delta_secs();
+        if !watch.sent && watch.past_for >= watch.duration {
+            watch.sent = true;
+            notis.write(watch.noti.clone());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{NotiBoxCommands, NotiBoxEvent, NotiLevel};
+use bevy::prelude::*;
+
+/// One-line notifications from [`Commands`] or a [`MessageWriter`].
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_noti_box::*;
+/// fn save(mut commands: Commands) {
+///     commands.notify("Saved!");
+/// }
+/// ```
+pub trait NotifyExt {
+    /// Send a notification
+    fn notify_with(&mut self, noti: NotiBoxEvent);
+
+    /// Send a notification with the default style
+    fn notify(&mut self, msg: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::from_message(msg.into()));
+    }
+
+    fn info(&mut self, msg: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::from_level(NotiLevel::Info, msg));
+    }
+
+    fn success(&mut self, msg: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::from_level(NotiLevel::Success, msg));
+    }
+
+    fn warning(&mut self, msg: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::from_level(NotiLevel::Warning, msg));
+    }
+
+    fn error(&mut self, msg: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::from_level(NotiLevel::Error, msg));
+    }
+
+    /// See [`NotiBoxEvent::snackbar`]
+    fn snackbar(&mut self, msg: impl Into<String>, payload: impl Into<String>) {
+        self.notify_with(NotiBoxEvent::snackbar(msg, payload));
+    }
+}
+
+impl NotifyExt for Commands<'_, '_> {
+    fn notify_with(&mut self, noti: NotiBoxEvent) {
+        self.write_message(noti);
+    }
+}
+
+impl NotifyExt for MessageWriter<'_, NotiBoxEvent> {
+    fn notify_with(&mut self, noti: NotiBoxEvent) {
+        self.write(noti);
+    }
+}
+
+impl NotifyExt for NotiBoxCommands<'_> {
+    fn notify_with(&mut self, noti: NotiBoxEvent) {
+        self.send(noti);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{NotiBoxEvent, NotiLevel};
+use bevy::prelude::*;
+use std::{collections::VecDeque, time::Duration};
+
+const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// A notification as recorded in [`NotiHistory`]
+#[derive(Clone, Debug)]
+pub struct NotiRecord {
+    /// Time since startup when the notification was received
+    pub time: Duration,
+    pub msg: String,
+    pub level: NotiLevel,
+    pub tag: Option<String>,
+}
+
+impl NotiRecord {
+    pub(crate) fn new(noti: &NotiBoxEvent, time: Duration) -> Self {
+        Self {
+            time,
+            msg: noti.msg.clone(),
+            level: noti.level,
+            tag: noti.tag.clone(),
+        }
+    }
+}
+
+/// The latest notifications, oldest first, e.g. to build a log screen
+#[derive(Resource)]
+pub struct NotiHistory {
+    records: VecDeque<NotiRecord>,
+    capacity: usize,
+}
+
+impl Default for NotiHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl NotiHistory {
+    /// Keep at most `capacity` records, dropping the oldest ones
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &NotiRecord> {
+        self.records.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    pub(crate) fn push(&mut self, record: NotiRecord) {
+        self.records.push_back(record);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+        }
+    }
+}
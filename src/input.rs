@@ -0,0 +1,186 @@
+use crate::{
+    click_noti_box, dismiss_noti_box, DismissReason, NotiBox, NotiBoxClicked, NotiBoxDropped, NotiBoxId,
+    NotiBoxSettings, NotiClock, NotiQueue,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use std::time::Duration;
+
+type NotiBoxQuery<'w, 's> = Query<'w, 's, (Entity, &'static NotiBoxId, &'static mut NotiBox)>;
+
+/// Dismiss the most recently shown notification that is not already going away
+fn dismiss_newest(commands: &mut Commands, query: &mut NotiBoxQuery) {
+    let newest = query
+        .iter_mut()
+        .filter(|(_, _, noti_box)| !noti_box.is_leaving())
+        .max_by_key(|(_, _, noti_box)| noti_box.shown_at);
+    if let Some((e, id, mut noti_box)) = newest {
+        dismiss_noti_box(commands, e, *id, &mut noti_box, DismissReason::Shortcut);
+    }
+}
+
+/// Dismiss every notification, including the queued ones
+fn dismiss_all(
+    commands: &mut Commands,
+    queue: &mut NotiQueue,
+    dropped: &mut MessageWriter<NotiBoxDropped>,
+    query: &mut NotiBoxQuery,
+) {
+    queue.remove(dropped, |_| true);
+    for (e, id, mut noti_box) in query.iter_mut() {
+        dismiss_noti_box(commands, e, *id, &mut noti_box, DismissReason::Shortcut);
+    }
+}
+
+pub(crate) fn listen_keyboard(
+    mut commands: Commands,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    settings: Res<NotiBoxSettings>,
+    mut queue: ResMut<NotiQueue>,
+    mut dropped: MessageWriter<NotiBoxDropped>,
+    mut query: NotiBoxQuery,
+) {
+    let (Some(keys), Some(key)) = (keys, settings.dismiss_key) else {
+        return;
+    };
+    if !keys.just_pressed(key) {
+        return;
+    }
+
+    if keys.any_pressed(settings.dismiss_all_modifiers.iter().copied()) {
+        dismiss_all(&mut commands, &mut queue, &mut dropped, &mut query);
+    } else {
+        dismiss_newest(&mut commands, &mut query);
+    }
+}
+
+pub(crate) fn listen_gamepad(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    settings: Res<NotiBoxSettings>,
+    mut query: NotiBoxQuery,
+) {
+    let Some(button) = settings.gamepad_dismiss_button else {
+        return;
+    };
+    if gamepads.iter().any(|gamepad| gamepad.just_pressed(button)) {
+        dismiss_newest(&mut commands, &mut query);
+    }
+}
+
+/// Speed of a notification swiped away, in percent of its width per second
+const SWIPE_FLING_SPEED: f32 = 400.;
+
+/// How fast a notification released before the swipe threshold goes back in place
+const SWIPE_SPRING_RATE: f32 = 15.;
+
+pub(crate) fn listen_swipe(
+    mut commands: Commands,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    touches: Option<Res<Touches>>,
+    settings: Res<NotiBoxSettings>,
+    time: NotiClock,
+    mut query: Query<(Entity, &NotiBoxId, &mut NotiBox, &Interaction, &ComputedNode)>,
+) {
+    if !settings.swipe_to_dismiss {
+        return;
+    }
+    let cursor = touches
+        .and_then(|touches| touches.first_pressed_position())
+        .or_else(|| windows.iter().find_map(Window::cursor_position));
+    let delta = time.delta().as_secs_f32();
+
+    for (e, id, mut noti_box, interaction, node) in query.iter_mut() {
+        if !noti_box.interactive {
+            continue;
+        }
+        if noti_box.fling != 0. {
+            if !settings.reduced_motion {
+                noti_box.drag += noti_box.fling * SWIPE_FLING_SPEED * delta;
+            }
+            continue;
+        }
+
+        let width = node.size().x * node.inverse_scale_factor();
+        match cursor {
+            Some(cursor) if *interaction == Interaction::Pressed && width > 0. && !noti_box.is_leaving() => {
+                let offset = noti_box.drag / 100. * width;
+                let start = *noti_box.drag_start.get_or_insert(cursor.x - offset);
+                noti_box.drag = (cursor.x - start) / width * 100.;
+            }
+            _ if noti_box.drag_start.is_some() && noti_box.drag.abs() >= settings.swipe_threshold * 100. => {
+                noti_box.drag_start = None;
+                noti_box.fling = noti_box.drag.signum();
+                dismiss_noti_box(&mut commands, e, *id, &mut noti_box, DismissReason::Swiped);
+            }
+            _ if noti_box.drag_start.is_some() || noti_box.drag != 0. => {
+                noti_box.drag_start = None;
+                noti_box.drag *= (-SWIPE_SPRING_RATE * delta).exp();
+                if settings.reduced_motion || noti_box.drag.abs() < 0.1 {
+                    noti_box.drag = 0.;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// How far, in logical pixels, a touch can move and still be a tap
+const TAP_SLOP: f32 = 10.;
+
+/// Notification being touched
+pub(crate) struct TouchPress {
+    noti_box: Entity,
+    touch: u64,
+    /// Time it started
+    start: Duration,
+    pinned: bool,
+}
+
+pub(crate) fn listen_touch(
+    mut commands: Commands,
+    touches: Option<Res<Touches>>,
+    settings: Res<NotiBoxSettings>,
+    time: NotiClock,
+    mut press: Local<Option<TouchPress>>,
+    mut clicked: MessageWriter<NotiBoxClicked>,
+    mut query: Query<(Entity, &NotiBoxId, &mut NotiBox, Ref<Interaction>)>,
+) {
+    let Some(touches) = touches else {
+        return;
+    };
+    if let Some(touch) = touches.iter_just_pressed().next() {
+        // The UI marks the touched notification as pressed in the same frame
+        *press = query
+            .iter()
+            .find(|(_, _, noti_box, i)| i.is_changed() && **i == Interaction::Pressed && noti_box.interactive)
+            .map(|(e, ..)| TouchPress {
+                noti_box: e,
+                touch: touch.id(),
+                start: time.elapsed(),
+                pinned: false,
+            });
+    }
+    let Some(current) = press.as_mut() else {
+        return;
+    };
+    let Ok((e, id, mut noti_box, _)) = query.get_mut(current.noti_box) else {
+        *press = None;
+        return;
+    };
+
+    if let Some(touch) = touches.get_pressed(current.touch) {
+        let held = time.elapsed().saturating_sub(current.start).as_secs_f32();
+        if !current.pinned && held >= settings.long_press_time && touch.distance().length() < TAP_SLOP {
+            current.pinned = true;
+            noti_box.pin();
+        }
+    } else if let Some(touch) = touches.get_released(current.touch) {
+        if !current.pinned && !noti_box.is_leaving() && touch.distance().length() < TAP_SLOP {
+            click_noti_box(&mut commands, &mut clicked, e, *id, &mut noti_box, &settings);
+        }
+        *press = None;
+    } else {
+        // Canceled
+        *press = None;
+    }
+}
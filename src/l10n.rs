@@ -0,0 +1,38 @@
+use crate::NotiBoxEvent;
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Turns message keys into text in the current language, e.g. backed by fluent
+pub trait NotiLocalize: Send + Sync + 'static {
+    /// Text of `key` formatted with `args`, `None` if the key is unknown
+    fn localize(&self, key: &str, args: &HashMap<String, String>) -> Option<String>;
+}
+
+impl<F> NotiLocalize for F
+where
+    F: Fn(&str, &HashMap<String, String>) -> Option<String> + Send + Sync + 'static,
+{
+    fn localize(&self, key: &str, args: &HashMap<String, String>) -> Option<String> {
+        self(key, args)
+    }
+}
+
+/// Localizer used for notifications sent with a [`NotiBoxEvent::msg_key`].
+/// Without it, or when a key is unknown, the key itself is shown.
+#[derive(Resource)]
+pub struct NotiLocalizer(Box<dyn NotiLocalize>);
+
+impl NotiLocalizer {
+    pub fn new(localizer: impl NotiLocalize) -> Self {
+        Self(Box::new(localizer))
+    }
+}
+
+/// Replace the message of `noti` by the text of its key
+pub(crate) fn localize(noti: &mut NotiBoxEvent, localizer: Option<&NotiLocalizer>) {
+    let Some(key) = noti.msg_key.take() else {
+        return;
+    };
+    noti.msg = localizer
+        .and_then(|localizer| localizer.0.localize(&key, &noti.msg_args))
+        .unwrap_or(key);
+}
@@ -1,25 +1,170 @@
-
-//!
+//! Notification boxes (toasts) for Bevy UI.
 //!
+use accesskit::{Node as AccessNode, Role};
 use bevy::{
-    app::{App, Plugin, Update},
-    color::{Alpha, Color},
+    a11y::AccessibilityNode,
+    app::{App, Plugin, PostUpdate, Update},
+    asset::UntypedAssetLoadFailedEvent,
+    camera::visibility::RenderLayers,
+    color::{Alpha, Color, Luminance},
+    ecs::{
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        system::SystemParam,
+    },
+    math::Affine2,
+    platform::collections::HashMap,
     prelude::*,
+    text::{LineHeight, TextLayoutInfo},
+    ui::{FocusPolicy, UiSystems},
+    window::{PrimaryWindow, WindowOccluded},
 };
+#[cfg(feature = "material")]
+use bevy::ui_render::prelude::{MaterialNode, UiMaterial};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::Duration,
+};
+
+pub use animation::{NotiAnimationTarget, NotiAnimation, NotiAnimator};
+pub use builder::NotiBoxBuilder;
+pub use channel::{NotiChannel, NotiChannels};
+pub use confirm::{NotiCancelled, NotiConfirmEvent, NotiConfirmed};
+pub use diagnostics::{NotiDiagnosticWatch, NotiDiagnostics};
+pub use ext::NotifyExt;
+pub use history::{NotiHistory, NotiRecord};
+#[cfg(feature = "l10n")]
+pub use l10n::{NotiLocalize, NotiLocalizer};
+pub use log_layer::{noti_log_layer, NotiLogSettings, LOG_CHANNEL};
+pub use native::NotiNativeRequest;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub use native::request_web_permission;
+#[cfg(feature = "picking")]
+pub use picking::{NotiPointerEvent, NotiPointerKind};
+pub use scheduler::{NotiScheduleId, NotiScheduler};
+pub use sender::NotiSender;
+pub use sequence::NotiSequenceEvent;
+pub use snackbar::UndoRequested;
+#[cfg(feature = "template")]
+pub use template::{NotiTemplate, NotiTemplateRef};
+pub use testing::{NotiAssertExt, NotiBoxTestPlugin, NotiRecorder};
+pub use theme::NotiBoxTheme;
+#[cfg(feature = "tweening")]
+pub use tweening::{NotiAlpha, NotiAlphaLens, NotiScaleLens, NotiTranslationLens, NotiTweens};
+pub use world::{FloatingTextEvent, WorldNotiEvent};
+
+use limit::RateLimiter;
+
+mod animation;
+mod asset_errors;
+#[cfg(feature = "audio")]
+mod audio;
+mod builder;
+mod channel;
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+mod clipboard;
+mod confirm;
+mod diagnostics;
+mod ext;
+mod history;
+mod input;
+#[cfg(feature = "l10n")]
+mod l10n;
+mod limit;
+mod log_layer;
+mod native;
+#[cfg(feature = "picking")]
+mod picking;
+mod pool;
+mod scheduler;
+mod sender;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serde_font;
+mod snackbar;
+#[cfg(feature = "template")]
+mod template;
+mod testing;
+mod theme;
+#[cfg(feature = "tweening")]
+mod tweening;
+mod world;
 
 macro_rules! plugin_systems {
     ( ) => {
-        (listen_event, listen_click, countdown)
+        (
+            (
+                listen_event,
+                listen_dismiss,
+                listen_update,
+                // Clicks come from `picking::on_click` instead
+                #[cfg(not(feature = "picking"))]
+                listen_click,
+                listen_close_button,
+                listen_copy_button,
+                listen_action_button,
+                countdown,
+            ),
+            (
+                update_progress_bar,
+                update_repeat_counter,
+                update_modal_overlay,
+                update_spinner,
+                pool::fill_pool,
+                update_containers,
+                type_text,
+                scroll_marquee,
+                truncate_text,
+                mark_ran,
+            ),
+            (
+                input::listen_keyboard,
+                input::listen_gamepad,
+                input::listen_swipe,
+                input::listen_touch,
+            ),
+            (world::listen_world_event, world::follow_world_target),
+            (
+                confirm::listen_confirm_event,
+                confirm::listen_confirm_answer,
+                snackbar::listen_undo,
+                sequence::listen_sequence,
+                scheduler::tick_scheduler,
+                log_layer::listen_log,
+                diagnostics::watch_diagnostics,
+                asset_errors::listen_asset_errors,
+                sender::listen_sender,
+            ),
+        )
     };
 }
 
-#[derive(Default)]
 pub struct NotiBoxPlugin<T>
 where
     T: States,
 {
     /// List of game state that this plugin will run in
     pub states: Vec<T>,
+    /// Schedule the plugin's systems run in, `Update` by default
+    pub schedule: InternedScheduleLabel,
+    /// Asset path and size of the default font, loaded into [`NotiBoxTheme::font`]
+    pub font: Option<(String, f32)>,
+    /// What happens to the notifications on screen when leaving `states`
+    pub state_policy: NotiStatePolicy,
+    /// Run conditions added with [`NotiBoxPlugin::run_if`], applied to [`NotiBoxSystems`]
+    conditions: Mutex<Vec<Box<dyn FnOnce(&mut App, InternedScheduleLabel) + Send>>>,
+}
+
+impl<T> Default for NotiBoxPlugin<T>
+where
+    T: States,
+{
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl<T> Plugin for NotiBoxPlugin<T>
@@ -27,15 +172,166 @@ where
     T: States,
 {
     fn build(&self, app: &mut App) {
-        app.add_message::<NotiBoxEvent>();
+        app.add_message::<NotiBoxEvent>()
+            .add_message::<DismissNoti>()
+            .add_message::<DismissAllNotiBoxes>()
+            .add_message::<UpdateNotiBox>()
+            .add_message::<NotiBoxShown>()
+            .add_message::<NotiBoxDismissed>()
+            .add_message::<NotiBoxDropped>()
+            .add_message::<NotiBoxClicked>()
+            .add_message::<NotiActionTriggered>()
+            .add_message::<NotiCopyRequested>()
+            .add_message::<NotiNativeRequest>()
+            // Already added by `WindowPlugin` and `AssetPlugin`, except in headless apps
+            .add_message::<WindowOccluded>()
+            .add_message::<UntypedAssetLoadFailedEvent>()
+            .add_message::<WorldNotiEvent>()
+            .add_message::<FloatingTextEvent>()
+            .add_message::<NotiConfirmEvent>()
+            .add_message::<NotiConfirmed>()
+            .add_message::<NotiCancelled>()
+            .add_message::<UndoRequested>()
+            .add_message::<NotiSequenceEvent>()
+            .init_resource::<NotiBoxSettings>()
+            .init_resource::<NotiBoxTheme>()
+            .init_resource::<NotiQueue>()
+            .init_resource::<NotiHistory>()
+            .init_resource::<NotiChannels>()
+            .init_resource::<confirm::PendingConfirms>()
+            .init_resource::<sequence::NotiSequences>()
+            .init_resource::<NotiScheduler>()
+            .init_resource::<pool::NotiPool>()
+            .init_resource::<NotiDiagnostics>()
+            .register_type::<NotiPosition>()
+            .register_type::<NotiLevel>()
+            .register_type::<NotiPriority>()
+            .register_type::<NotiAnimation>()
+            .register_type::<NotiBoxId>()
+            .register_type::<NotiBoxEvent>()
+            .register_type::<NotiBoxSettings>()
+            .register_type::<NotiBoxTheme>()
+            .register_type::<NotiChannels>()
+            .register_type::<NotiBox>();
+
+        // Templates need the asset plugin, added before this one
+        #[cfg(feature = "template")]
+        if app.world().contains_resource::<AssetServer>() {
+            app.init_asset::<NotiTemplate>()
+                .init_asset_loader::<template::NotiTemplateLoader>();
+        }
+
+        let conditions = std::mem::take(&mut *self.conditions.lock().unwrap_or_else(PoisonError::into_inner));
+        for condition in conditions {
+            condition(app, self.schedule);
+        }
 
         if self.states.is_empty() {
-            app.add_systems(Update, plugin_systems!());
+            app.add_systems(self.schedule, plugin_systems!().in_set(NotiBoxSystems));
         } else {
-            for state in self.states.iter() {
-                app.add_systems(Update, plugin_systems!().run_if(in_state(state.clone())));
-            }
+            // Register the systems once, running in any of the states
+            let states = self.states.clone();
+            app.add_systems(
+                self.schedule,
+                plugin_systems!()
+                    .in_set(NotiBoxSystems)
+                    .run_if(move |current: Option<Res<State<T>>>| {
+                        current.is_some_and(|current| states.contains(current.get()))
+                    }),
+            );
+
+            let states = self.states.clone();
+            let policy = self.state_policy;
+            app.add_systems(
+                self.schedule,
+                move |mut transitions: MessageReader<StateTransitionEvent<T>>,
+                      mut commands: Commands,
+                      mut queue: ResMut<NotiQueue>,
+                      mut dropped: MessageWriter<NotiBoxDropped>,
+                      noti_boxes: Query<(Entity, &NotiBoxId), With<NotiBox>>,
+                      roots: NotiRoots| {
+                    for transition in transitions.read() {
+                        if transition.exited == transition.entered {
+                            continue;
+                        }
+                        let exited = transition.exited.as_ref().is_some_and(|s| states.contains(s));
+                        let entered = transition.entered.as_ref().is_some_and(|s| states.contains(s));
+                        let despawn = match policy {
+                            NotiStatePolicy::Despawn => exited && !entered,
+                            NotiStatePolicy::StateScoped => exited,
+                            _ => false,
+                        };
+                        if despawn {
+                            queue.remove(&mut dropped, |_| true);
+                            for (e, id) in noti_boxes.iter() {
+                                despawn_noti_box(&mut commands, e, *id, DismissReason::StateExit);
+                            }
+                            // After the notifications went back to the pool, some with their overlay
+                            for e in roots.iter() {
+                                commands.entity(e).try_despawn();
+                            }
+                        } else if policy == NotiStatePolicy::Pause && exited != entered {
+                            let visibility = if entered {
+                                Visibility::Inherited
+                            } else {
+                                Visibility::Hidden
+                            };
+                            for e in roots.iter() {
+                                commands.entity(e).insert(visibility);
+                            }
+                        }
+                    }
+                },
+            );
         }
+
+        app.add_observer(forward_triggered);
+        sender::init(app);
+        #[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+        app.add_systems(self.schedule, native::show_native.in_set(NotiBoxSystems));
+        #[cfg(all(feature = "web", target_arch = "wasm32"))]
+        app.add_systems(self.schedule, native::show_web.in_set(NotiBoxSystems));
+        #[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+        app.add_systems(self.schedule, clipboard::copy_to_clipboard.in_set(NotiBoxSystems));
+        // Cleared before the plugin's systems mark it again, so it is off with their states and run conditions
+        app.init_resource::<NotiSystemsActive>().add_systems(
+            self.schedule,
+            (|mut active: ResMut<NotiSystemsActive>| active.0 = false).before(NotiBoxSystems),
+        );
+        #[cfg(feature = "picking")]
+        app.add_message::<NotiPointerEvent>()
+            .add_observer(picking::on_click)
+            .add_observer(picking::on_over)
+            .add_observer(picking::on_out);
+
+        // Runs on the fresh layout, before the notifications are drawn at their new place.
+        // It is outside of the plugin's schedule, so it follows its systems instead of their run conditions.
+        app.init_resource::<NotiSystemsRan>().add_systems(
+            PostUpdate,
+            reflow
+                .run_if(|ran: Res<NotiSystemsRan>| ran.0)
+                .after(UiSystems::Layout)
+                .before(UiSystems::PostLayout),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some((path, font_size)) = self.font.clone() else {
+            return;
+        };
+        let Some(font) = app
+            .world()
+            .get_resource::<AssetServer>()
+            .map(|server| server.load(path))
+        else {
+            warn!("Default notification font not loaded because there is no asset server");
+            return;
+        };
+        app.world_mut().resource_mut::<NotiBoxTheme>().font = TextFont {
+            font,
+            font_size,
+            ..default()
+        };
     }
 }
 
@@ -44,10 +340,77 @@ where
     T: States,
 {
     pub fn new(states: Vec<T>) -> Self {
-        Self { states }
+        Self {
+            states,
+            schedule: Update.intern(),
+            font: None,
+            state_policy: NotiStatePolicy::default(),
+            conditions: Mutex::default(),
+        }
+    }
+
+    /// Show notifications with the font at `path` by default, instead of Bevy's built-in one
+    pub fn with_font(mut self, path: impl Into<String>, font_size: f32) -> Self {
+        self.font = Some((path.into(), font_size));
+        self
     }
+
+    pub fn with_state_policy(mut self, state_policy: NotiStatePolicy) -> Self {
+        self.state_policy = state_policy;
+        self
+    }
+
+    /// Run the plugin's systems in `schedule` instead of `Update`, e.g. `FixedUpdate`
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Only run the plugin's systems when `condition` is true, e.g. not while a pause menu is open.
+    /// Can be called several times, every condition must be true.
+    pub fn run_if<M>(self, condition: impl SystemCondition<M> + Send + 'static) -> Self {
+        self.conditions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(move |app: &mut App, schedule| {
+                app.configure_sets(schedule, NotiBoxSystems.run_if(condition));
+            }));
+        self
+    }
+}
+
+/// What happens to the notifications on screen when leaving the states [`NotiBoxPlugin`] runs in,
+/// where they stop fading and counting down
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NotiStatePolicy {
+    /// Leave them frozen on screen
+    #[default]
+    Keep,
+    /// Hide them until coming back to one of the states, then carry on
+    Pause,
+    /// Dismiss them right away with [`DismissReason::StateExit`], queued ones included
+    Despawn,
+    /// Dismiss them like `Despawn` when leaving the state they were shown in, even for another state of the plugin
+    StateScoped,
 }
 
+/// Top-level entities of the notifications on screen
+type NotiRoots<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    Or<(
+        With<NotiContainer>,
+        With<NotiModalOverlay>,
+        With<world::NotiWorldAnchor>,
+    )>,
+>;
+
+/// Every system of the plugin, to order your own systems against, e.g. `.after(NotiBoxSystems)` to see the
+/// notifications spawned this frame
+#[derive(SystemSet, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct NotiBoxSystems;
+
 #[derive(States, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum DummyState {}
 
@@ -59,13 +422,32 @@ impl NotiBoxPluginAnyState {
     pub fn any() -> NotiBoxPlugin<DummyState> {
         NotiBoxPlugin::new(Vec::new())
     }
+
+    /// Run this plugin's systems whenever `condition` is true
+    pub fn run_if<M>(condition: impl SystemCondition<M> + Send + 'static) -> NotiBoxPlugin<DummyState> {
+        Self::any().run_if(condition)
+    }
 }
 
 const BACKGROUND_COLOR: Color = Color::BLACK;
 
 const DEFAULT_ANIMATION_DURATION: f32 = 0.5;
 
-#[derive(Default)]
+const ICON_SIZE: f32 = 32.;
+
+const SPINNER_SIZE: f32 = 20.;
+
+/// Turns per second of a spinner
+const SPINNER_SPEED: f32 = 1.5;
+
+/// Longest fade in seconds with [`NotiBoxSettings::reduced_motion`]
+const REDUCED_MOTION_FADE: f32 = 0.15;
+
+/// Maximum number of action buttons of a notification
+pub const MAX_ACTIONS: usize = 3;
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NotiPosition {
     #[default]
     TopRight,
@@ -77,9 +459,33 @@ pub enum NotiPosition {
     BotLeft,
     BotMid,
     BotRight,
+    /// Top left corner of the stack at an exact spot of the screen, e.g. right under the minimap
+    Custom {
+        left: Val,
+        top: Val,
+    },
+}
+
+impl Eq for NotiPosition {}
+
+impl std::hash::Hash for NotiPosition {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let NotiPosition::Custom { left, top } = self {
+            for val in [left, top] {
+                std::mem::discriminant(val).hash(state);
+                let value = match *val {
+                    Val::Auto => 0.,
+                    Val::Px(v) | Val::Percent(v) | Val::Vw(v) | Val::Vh(v) | Val::VMin(v) | Val::VMax(v) => v,
+                };
+                // `0.` and `-0.` are equal
+                (value + 0.).to_bits().hash(state);
+            }
+        }
+    }
 }
 
-#[derive(Default, PartialEq)]
+#[derive(Default, PartialEq, Reflect)]
 enum AnimationState {
     #[default]
     Start,
@@ -87,180 +493,2645 @@ enum AnimationState {
     End,
 }
 
-#[derive(Message)]
+/// Severity of a notification
+#[derive(Default, Clone, Copy, Debug, Hash, Eq, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotiLevel {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl NotiLevel {
+    pub fn background_color(&self) -> Color {
+        match self {
+            NotiLevel::Info => Color::srgb(0.12, 0.32, 0.55),
+            NotiLevel::Success => Color::srgb(0.12, 0.42, 0.2),
+            NotiLevel::Warning => Color::srgb(0.8, 0.55, 0.05),
+            NotiLevel::Error => Color::srgb(0.6, 0.1, 0.1),
+        }
+    }
+
+    pub fn text_color(&self) -> Color {
+        match self {
+            NotiLevel::Warning => Color::BLACK,
+            _ => Color::WHITE,
+        }
+    }
+
+    pub fn border_color(&self) -> Color {
+        match self {
+            NotiLevel::Info => Color::srgb(0.35, 0.6, 0.9),
+            NotiLevel::Success => Color::srgb(0.3, 0.8, 0.4),
+            NotiLevel::Warning => Color::srgb(1., 0.8, 0.3),
+            NotiLevel::Error => Color::srgb(0.95, 0.3, 0.3),
+        }
+    }
+}
+
+/// Importance of a notification when there are more than [`NotiBoxSettings::max_visible`]
+#[derive(Default, Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotiPriority {
+    Low,
+    #[default]
+    Normal,
+    /// Jump ahead of the queue and replace the oldest visible notification of lower priority if needed
+    High,
+}
+
+/// What happens to a notification arriving where the maximum number of notifications is reached
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotiOverflow {
+    /// The oldest visible one goes away
+    #[default]
+    DropOldest,
+    /// The new one is dropped
+    DropNewest,
+    /// The new one waits until there is room
+    Queue,
+}
+
+/// Where a new notification goes in the stack of its position
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotiStackOrder {
+    /// Below the older ones, the stack grows downward from the top
+    #[default]
+    NewestBelow,
+    /// Above the older ones, the stack grows upward from the bottom
+    NewestAbove,
+}
+
+/// Maximum number of notifications on screen at once somewhere, e.g. at a position
+#[derive(Clone, Copy, Debug, Default, Reflect)]
+pub struct NotiLimit {
+    /// `0` means no limit
+    pub max_visible: usize,
+    pub overflow: NotiOverflow,
+}
+
+impl NotiLimit {
+    pub fn new(max_visible: usize, overflow: NotiOverflow) -> Self {
+        Self { max_visible, overflow }
+    }
+}
+
+/// Global configuration of the plugin.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct NotiBoxSettings {
+    /// Show notifications. If `false`, they are dropped, e.g. for a "Disable notifications" option.
+    pub enabled: bool,
+    /// Still record notifications in [`NotiHistory`] while `enabled` is `false`
+    pub record_disabled: bool,
+    /// Hold notifications back, e.g. during a cutscene or a boss fight. They are shown once it is `false` again.
+    pub do_not_disturb: bool,
+    /// Show a single summary instead of the held back notifications if there are more than this many.
+    /// `0` to always show them all.
+    pub summarize_over: usize,
+    /// Message of the summary, with `{count}` replaced by the number of held back notifications
+    pub summary_msg: String,
+    /// Maximum number of notifications on screen at once, not counting the ones fading out.
+    /// Extra ones wait in a queue. `0` means no limit.
+    pub max_visible: usize,
+    /// Maximum number of notifications on screen at once at some positions
+    pub position_limits: HashMap<NotiPosition, NotiLimit>,
+    /// Order of the notifications at some positions, e.g. [`NotiStackOrder::NewestAbove`] at the bottom ones.
+    /// [`NotiStackOrder::NewestBelow`] elsewhere.
+    pub stack_orders: HashMap<NotiPosition, NotiStackOrder>,
+    /// Distance between the screen edges and the stack of notifications at some positions,
+    /// e.g. to keep clear of a HUD bar
+    pub offsets: HashMap<NotiPosition, UiRect>,
+    /// Icon shown next to the message of each level
+    pub level_icons: HashMap<NotiLevel, Handle<Image>>,
+    /// Stop the show time countdown while the cursor is over a notification
+    pub pause_on_hover: bool,
+    /// Instead of showing a notification with the same text and position as a visible one,
+    /// restart the show time of the visible one and display a repeat counter.
+    /// Notifications with an id, a progress, a spinner or actions are always shown.
+    pub deduplicate: bool,
+    /// UI node to show notifications in instead of the root of the UI
+    pub parent: Option<Entity>,
+    /// Camera to render notifications with instead of the default UI camera. Ignored if `parent` is set.
+    pub camera: Option<Entity>,
+    /// Space kept free at the edges of the screen, e.g. for phone notches and rounded corners.
+    /// Bevy does not report the safe area of a window, so set it from the platform if needed.
+    /// Not applied to [`NotiPosition::Custom`].
+    pub safe_area: UiRect,
+    /// Drop notifications beyond this many per second. `0` means no limit.
+    pub max_per_second: u32,
+    /// Drop notifications whose message was already sent less than this many seconds ago. `0.` means no limit.
+    pub repeat_interval: f32,
+    /// Count time with `Time<Real>`, so notifications keep expiring while the virtual time is paused
+    pub real_time: bool,
+    /// Key dismissing the newest notification. `None` to disable.
+    pub dismiss_key: Option<KeyCode>,
+    /// Holding any of these with `dismiss_key` dismisses every notification, queued ones included
+    pub dismiss_all_modifiers: Vec<KeyCode>,
+    /// Gamepad button dismissing the newest notification. `None` to disable.
+    pub gamepad_dismiss_button: Option<GamepadButton>,
+    /// Drag notifications sideways with the mouse or touch to dismiss them.
+    /// Clicking the body then only sends [`NotiBoxClicked`] instead of dismissing.
+    pub swipe_to_dismiss: bool,
+    /// Fraction of its width a notification must be dragged to be dismissed
+    pub swipe_threshold: f32,
+    /// Seconds a notification must be touched to be pinned, stopping its show time. Tapping it acts as a click.
+    pub long_press_time: f32,
+    /// For players sensitive to motion: notifications only fade, quickly, instead of sliding or growing
+    pub reduced_motion: bool,
+    /// Seconds a [`NotiBoxEvent::progress`] notification stays once complete
+    pub progress_linger: f32,
+    /// Keep this many hidden entities to show notifications on instead of spawning new ones, e.g. for a kill feed.
+    /// Entities mentioned by [`NotiBoxDismissed`] may then be reused. Notifications with [`NotiBoxEvent::insert`]ed
+    /// components are never reused.
+    pub pool_size: usize,
+    /// Send notifications as [`NotiNativeRequest`] instead of showing them while the window is unfocused or
+    /// hidden, e.g. in a background browser tab, to show them with the OS or the browser instead, see the `native`
+    /// feature
+    pub native_when_unfocused: bool,
+    /// Show an error notification when an asset fails to load, e.g. to notice missing files during development
+    pub asset_errors: bool,
+}
+
+impl Default for NotiBoxSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            record_disabled: false,
+            do_not_disturb: false,
+            summarize_over: 3,
+            summary_msg: "{count} notifications received".to_string(),
+            max_visible: 5,
+            position_limits: HashMap::default(),
+            stack_orders: HashMap::default(),
+            offsets: HashMap::default(),
+            level_icons: HashMap::default(),
+            pause_on_hover: true,
+            deduplicate: true,
+            parent: None,
+            camera: None,
+            safe_area: UiRect::DEFAULT,
+            max_per_second: 0,
+            repeat_interval: 0.,
+            real_time: false,
+            dismiss_key: Some(KeyCode::Delete),
+            dismiss_all_modifiers: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            gamepad_dismiss_button: Some(GamepadButton::East),
+            swipe_to_dismiss: false,
+            swipe_threshold: 0.4,
+            long_press_time: 0.5,
+            reduced_motion: false,
+            progress_linger: 1.,
+            pool_size: 0,
+            native_when_unfocused: false,
+            asset_errors: false,
+        }
+    }
+}
+
+/// Identifies a notification so it can be dismissed or updated later.
+#[derive(Component, Clone, Copy, Debug, Hash, Eq, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct NotiBoxId(u64);
+
+impl NotiBoxId {
+    /// Generate an id that is different from every one generated before
+    pub fn unique() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Fade out then despawn the notification with this id
+#[derive(Message, Clone, Copy)]
+pub struct DismissNoti(pub NotiBoxId);
+
+/// Fade out then despawn every notification, optionally only the ones matching the filters
+#[derive(Message, Clone, Default)]
+pub struct DismissAllNotiBoxes {
+    /// Only dismiss notifications at this position
+    pub pos: Option<NotiPosition>,
+    /// Only dismiss notifications with this tag
+    pub tag: Option<String>,
+}
+
+impl DismissAllNotiBoxes {
+    fn matches(&self, pos: NotiPosition, tag: &Option<String>) -> bool {
+        self.pos.is_none_or(|p| p == pos) && (self.tag.is_none() || self.tag == *tag)
+    }
+}
+
+/// Change the content of a visible or queued notification
+#[derive(Message, Clone)]
+pub struct UpdateNotiBox {
+    pub id: NotiBoxId,
+    /// New message, unchanged if `None`
+    pub msg: Option<String>,
+    /// New show time in seconds, restarted from zero. Unchanged if `None`.
+    pub show_time: Option<f32>,
+    /// New fill of the bar of a [`NotiBoxEvent::progress`] notification. Unchanged if `None`.
+    pub progress: Option<f32>,
+}
+
+impl UpdateNotiBox {
+    pub fn msg(id: NotiBoxId, msg: impl Into<String>) -> Self {
+        Self {
+            id,
+            msg: Some(msg.into()),
+            show_time: None,
+            progress: None,
+        }
+    }
+
+    pub fn progress(id: NotiBoxId, progress: f32) -> Self {
+        Self {
+            id,
+            msg: None,
+            show_time: None,
+            progress: Some(progress),
+        }
+    }
+}
+
+/// Sent when a notification has finished fading in
+#[derive(Message, Clone, Copy, Debug)]
+pub struct NotiBoxShown {
+    pub entity: Entity,
+    pub id: NotiBoxId,
+}
+
+/// Why a notification went away
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Reflect)]
+pub enum DismissReason {
+    /// Its show time ran out
+    Timeout,
+    /// The user clicked on it
+    Clicked,
+    /// It was dismissed with [`DismissNoti`] or [`DismissAllNotiBoxes`]
+    Api,
+    /// One of its action buttons was clicked
+    Action,
+    /// It made room for a notification of higher priority
+    Evicted,
+    /// It was dismissed with [`NotiBoxSettings::dismiss_key`] or [`NotiBoxSettings::gamepad_dismiss_button`]
+    Shortcut,
+    /// It was swiped away, see [`NotiBoxSettings::swipe_to_dismiss`]
+    Swiped,
+    /// The entity followed by its [`WorldNotiEvent`] was despawned
+    TargetLost,
+    /// The states the plugin runs in were left, see [`NotiStatePolicy::Despawn`]
+    StateExit,
+}
+
+/// Sent when a notification is despawned
+#[derive(Message, Clone, Copy, Debug)]
+pub struct NotiBoxDismissed {
+    pub entity: Entity,
+    pub id: NotiBoxId,
+    pub reason: DismissReason,
+}
+
+/// Sent when a notification with an id is never shown, e.g. muted, rate limited, merged into a duplicate or removed
+/// from the queue
+#[derive(Message, Clone, Copy, Debug)]
+pub struct NotiBoxDropped {
+    pub id: NotiBoxId,
+}
+
+/// Triggered on a notification entity when it is despawned, for observers added to it, e.g. in
+/// [`NotiBoxEvent::with`]
+#[derive(EntityEvent, Clone, Copy, Debug)]
+pub struct OnNotiDismissed {
+    pub entity: Entity,
+    pub id: NotiBoxId,
+    pub reason: DismissReason,
+}
+
+/// Sent when the body of a notification is clicked
+#[derive(Message, Clone, Copy, Debug)]
+pub struct NotiBoxClicked {
+    pub entity: Entity,
+    pub id: NotiBoxId,
+}
+
+/// Sent when the copy button of a notification is clicked, with its message to put in the clipboard.
+/// The `clipboard` feature copies it with the `arboard` crate, except on the web.
+#[derive(Message, Clone, Debug)]
+pub struct NotiCopyRequested {
+    pub id: NotiBoxId,
+    pub text: String,
+}
+
+/// Button shown under the message of a notification
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotiAction {
+    pub label: String,
+    /// Passed back in [`NotiActionTriggered`] so the game knows what to do
+    pub payload: String,
+}
+
+impl NotiAction {
+    pub fn new(label: impl Into<String>, payload: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            payload: payload.into(),
+        }
+    }
+}
+
+/// Sent when an action button is clicked. The notification is dismissed afterward.
+#[derive(Message, Clone, Debug)]
+pub struct NotiActionTriggered {
+    pub id: NotiBoxId,
+    pub action: NotiAction,
+}
+
+/// Piece of text appended to the message of a notification, with its own style
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NotiTextSpan {
+    pub text: String,
+    /// Falls back to the text color of the notification
+    pub color: Option<Color>,
+    /// Falls back to the font of the notification
+    #[cfg_attr(feature = "serde", serde(with = "serde_font::option_font"))]
+    pub font: Option<TextFont>,
+}
+
+impl NotiTextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..default()
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_font(mut self, font: TextFont) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+/// Notification to show. Send it as a message, or trigger it with [`Commands::trigger`].
+/// A triggered notification is sent as a message too: it is not spawned right away, but queued until the
+/// systems of the plugin run.
+#[derive(Message, Event, Clone, Reflect)]
+#[reflect(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct NotiBoxEvent {
+    /// Generated when the notification is shown if `None`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub id: Option<NotiBoxId>,
     pub msg: String,
+    /// Styled text shown after `msg`, e.g. to highlight an item name
+    pub spans: Vec<NotiTextSpan>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_font::font"))]
     pub font: TextFont,
+    /// Asset path of the font, loaded when the notification is shown. Replaces the font handle of `font`.
+    pub font_path: Option<String>,
     pub text_color: Color,
     pub pos: NotiPosition,
+    /// Seconds the notification stays fully shown.
+    /// If `<= 0.`, it is sticky: it stays until clicked or dismissed.
     pub show_time: f32,
+    /// Seconds to wait before showing the notification
+    pub delay: f32,
     pub background_color: BackgroundColor,
+    /// Image covering the background, e.g. UI art sliced with [`NodeImageMode::Sliced`] so its corners keep their
+    /// size. Drawn over `background_color`. Falls back to [`NotiBoxTheme::background_image`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub background_image: Option<ImageNode>,
+    /// Border color. Derived from `background_color` if `None`.
+    pub border_color: Option<Color>,
+    /// Border width, [`UiRect::DEFAULT`] for no border. Falls back to [`NotiBoxTheme::border`].
+    pub border: Option<UiRect>,
+    /// Falls back to [`NotiBoxTheme::border_radius`]
+    pub border_radius: Option<BorderRadius>,
+    /// Rendering order over the rest of the UI. Falls back to [`NotiBoxTheme::z_index`].
+    /// Give critical alerts a higher one to keep them above other notifications.
+    pub z_index: Option<i32>,
+    pub level: NotiLevel,
+    pub priority: NotiPriority,
+    /// Image shown next to the message. Falls back to the icon of `level` in [`NotiBoxSettings::level_icons`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub icon: Option<Handle<Image>>,
+    /// Free-form label to group notifications, e.g. to dismiss them together
+    pub tag: Option<String>,
+    /// Name of the [`NotiChannel`] in [`NotiChannels`] giving the defaults of the notification.
+    /// Ignored by world notifications.
+    pub channel: Option<String>,
+    /// Show a close ("X") button. Clicking the body then only sends [`NotiBoxClicked`] instead of dismissing.
+    pub close_button: bool,
+    /// Show a "Copy" button sending [`NotiCopyRequested`], e.g. for error messages with a code to report
+    pub copy_button: bool,
+    /// Buttons shown under the message, at most [`MAX_ACTIONS`]
+    pub actions: Vec<NotiAction>,
+    /// Show a thin bar at the bottom shrinking with the remaining show time
+    pub progress_bar: bool,
+    /// Show a labeled bar filled to this fraction, e.g. for a download, updated with [`UpdateNotiBox::progress`].
+    /// `show_time` is ignored: the notification stays until it is complete, then for
+    /// [`NotiBoxSettings::progress_linger`].
+    pub progress: Option<f32>,
+    /// Show a spinning indicator, e.g. while loading. `show_time` is ignored: the notification stays until it is
+    /// dismissed.
+    pub spinner: bool,
+    /// Dim the screen behind the notification and block the rest of the UI until it is gone, e.g. for critical
+    /// alerts. Ignored by world notifications.
+    pub modal: bool,
+    /// React to the cursor. If `false`, clicks go through to what is underneath and the close and action buttons
+    /// are not shown.
+    pub interactive: bool,
+    /// Animation played when the notification appears, and reversed when it goes away unless `exit` is set
+    pub entrance: NotiAnimation,
+    /// Animation played backward when the notification goes away, e.g. slide in but fade out
+    pub exit: Option<NotiAnimation>,
+    /// Curve applied to the fade and slide of `entrance` and `exit`
+    pub easing: EaseFunction,
+    /// Custom animation used instead of `entrance`, `exit` and `easing`
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub animator: Option<Arc<dyn NotiAnimator>>,
+    /// Entrance duration in seconds. Falls back to [`NotiBoxTheme::fade_in`].
+    pub fade_in: Option<f32>,
+    /// Exit duration in seconds. Falls back to [`NotiBoxTheme::fade_out`].
+    pub fade_out: Option<f32>,
+    /// UI node to show the notification in. Falls back to [`NotiBoxSettings::parent`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub parent: Option<Entity>,
+    /// Camera to render the notification with. Falls back to [`NotiBoxSettings::camera`].
+    /// Ignored if the notification has a parent.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub camera: Option<Entity>,
+    /// Render the notification with the active camera on one of these layers, the one with the highest order
+    /// if several are. Ignored if `camera` is set.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub render_layers: Option<RenderLayers>,
+    /// Played when the notification appears. Falls back to [`NotiBoxTheme::show_sound`].
+    #[cfg(feature = "audio")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub show_sound: Option<Handle<AudioSource>>,
+    /// Played when the notification goes away. Falls back to [`NotiBoxTheme::dismiss_sound`].
+    #[cfg(feature = "audio")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub dismiss_sound: Option<Handle<AudioSource>>,
+    /// Localized through [`NotiLocalizer`] into `msg` when the notification is received
+    #[cfg(feature = "l10n")]
+    pub msg_key: Option<String>,
+    /// Arguments passed to [`NotiLocalizer`] with `msg_key`
+    #[cfg(feature = "l10n")]
+    pub msg_args: HashMap<String, String>,
+    /// Built from this template once it is loaded, see [`NotiBoxEvent::from_template`]
+    #[cfg(feature = "template")]
+    #[serde(skip)]
+    pub template: Option<NotiTemplateRef>,
+    /// Run on the spawned notification entity, see [`NotiBoxEvent::with`]
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extras: NotiBoxExtras,
+    /// Taken from the theme if [`Val::Auto`]
     pub width: Val,
+    /// Taken from the theme if [`Val::Auto`]
     pub height: Val,
+    /// Width past which the message wraps. Falls back to [`NotiBoxTheme::max_width`].
+    pub max_width: Option<Val>,
+    /// Falls back to [`NotiBoxTheme::min_width`]
+    pub min_width: Option<Val>,
+    /// Falls back to [`NotiBoxTheme::min_height`]
+    pub min_height: Option<Val>,
+    /// Falls back to [`NotiBoxTheme::max_height`]
+    pub max_height: Option<Val>,
+    /// How the message wraps. Falls back to [`NotiBoxTheme::linebreak`].
+    pub linebreak: Option<LineBreak>,
+    /// Alignment of the lines of the message. Falls back to [`NotiBoxTheme::justify`].
+    pub justify: Option<Justify>,
+    /// Replaces the line height of `font` and of the fonts of `spans`
+    #[cfg_attr(feature = "serde", serde(with = "serde_font::option_line_height"))]
+    pub line_height: Option<LineHeight>,
+    /// Horizontal alignment of the content. Falls back to [`NotiBoxTheme::align_items`].
+    pub align_items: Option<AlignItems>,
+    /// Vertical alignment of the content, e.g. in a notification taller than its text.
+    /// Falls back to [`NotiBoxTheme::justify_content`].
+    pub justify_content: Option<JustifyContent>,
+    /// Lines of the message shown before it is cut with "…". Clicking the notification then shows the whole message
+    /// and restarts its show time instead of dismissing it.
+    pub max_lines: Option<usize>,
+    /// Characters per second the message is typed at, e.g. for RPG dialogues. `0.` to show it at once.
+    /// The show time starts meanwhile, so leave long messages enough of it.
+    pub typewriter: f32,
+    /// Logical pixels per second the message scrolls at, in a loop on a single line, if it does not fit.
+    /// `0.` to wrap it instead.
+    pub marquee: f32,
 }
 
+/// Callbacks adding the caller's components to a spawned notification
+#[derive(Clone, Default)]
+pub struct NotiBoxExtras(Vec<Arc<dyn Fn(&mut EntityCommands) + Send + Sync>>);
+
 impl Default for NotiBoxEvent {
     fn default() -> Self {
         Self {
+            id: None,
             msg: String::new(),
+            spans: Vec::new(),
             font: TextFont::default(),
+            font_path: None,
             text_color: Color::WHITE,
             pos: NotiPosition::default(),
             show_time: 5.,
+            delay: 0.,
             background_color: BACKGROUND_COLOR.into(),
-            width: Val::Percent(20.),
-            height: Val::Percent(20.),
+            background_image: None,
+            border_color: None,
+            border: None,
+            border_radius: None,
+            z_index: None,
+            level: NotiLevel::default(),
+            priority: NotiPriority::default(),
+            icon: None,
+            tag: None,
+            channel: None,
+            close_button: false,
+            copy_button: false,
+            actions: Vec::new(),
+            progress_bar: false,
+            progress: None,
+            spinner: false,
+            modal: false,
+            interactive: true,
+            entrance: NotiAnimation::default(),
+            exit: None,
+            animator: None,
+            easing: EaseFunction::Linear,
+            fade_in: None,
+            fade_out: None,
+            parent: None,
+            camera: None,
+            render_layers: None,
+            #[cfg(feature = "audio")]
+            show_sound: None,
+            #[cfg(feature = "audio")]
+            dismiss_sound: None,
+            #[cfg(feature = "l10n")]
+            msg_key: None,
+            #[cfg(feature = "l10n")]
+            msg_args: HashMap::default(),
+            #[cfg(feature = "template")]
+            template: None,
+            extras: NotiBoxExtras::default(),
+            width: Val::Auto,
+            height: Val::Auto,
+            max_width: None,
+            min_width: None,
+            min_height: None,
+            max_height: None,
+            linebreak: None,
+            justify: None,
+            line_height: None,
+            align_items: None,
+            justify_content: None,
+            max_lines: None,
+            typewriter: 0.,
+            marquee: 0.,
         }
     }
 }
 
 impl NotiBoxEvent {
+    /// Text of the message and spans, `None` if the notification is never merged into a visible duplicate:
+    /// the sender may refer to it by id, or it shows a state of its own
+    fn duplicate_key(&self) -> Option<Vec<String>> {
+        if self.id.is_some() || self.progress.is_some() || self.spinner || !self.actions.is_empty() {
+            return None;
+        }
+        let spans = self.spans.iter().map(|span| &span.text);
+        Some(std::iter::once(&self.msg).chain(spans).cloned().collect())
+    }
+
+    /// Load the font of `font_path` into `font`
+    pub(crate) fn load_font(&mut self, asset_server: Option<&AssetServer>) {
+        if let (Some(path), Some(asset_server)) = (self.font_path.take(), asset_server) {
+            self.font.font = asset_server.load(path);
+        }
+    }
+
     pub fn from_message(msg: String) -> Self {
         NotiBoxEvent { msg, ..default() }
     }
+
+    /// Create a notification styled after `level`
+    pub fn from_level(level: NotiLevel, msg: impl Into<String>) -> Self {
+        NotiBoxEvent {
+            msg: msg.into(),
+            text_color: level.text_color(),
+            background_color: level.background_color().into(),
+            border_color: Some(level.border_color()),
+            level,
+            ..default()
+        }
+    }
+
+    pub fn info(msg: impl Into<String>) -> Self {
+        Self::from_level(NotiLevel::Info, msg)
+    }
+
+    pub fn success(msg: impl Into<String>) -> Self {
+        Self::from_level(NotiLevel::Success, msg)
+    }
+
+    pub fn warning(msg: impl Into<String>) -> Self {
+        Self::from_level(NotiLevel::Warning, msg)
+    }
+
+    pub fn error(msg: impl Into<String>) -> Self {
+        Self::from_level(NotiLevel::Error, msg)
+    }
+
+    /// Run `f` on the notification entity once it is spawned, e.g. to insert components
+    pub fn with(mut self, f: impl Fn(&mut EntityCommands) + Send + Sync + 'static) -> Self {
+        self.extras.0.push(Arc::new(f));
+        self
+    }
+
+    /// Insert `bundle` on the notification entity once it is spawned, so it can be found with a query
+    pub fn insert(self, bundle: impl Bundle + Clone) -> Self {
+        self.with(move |entity| {
+            entity.insert(bundle.clone());
+        })
+    }
+
+    /// Draw the background with `material`, e.g. a gradient or an animated glow, over `background_color`.
+    /// The material is not faded with the notification, use a [`NotiAnimator`] to drive it.
+    #[cfg(feature = "material")]
+    pub fn with_material<M: UiMaterial>(self, material: Handle<M>) -> Self {
+        self.with(move |entity| {
+            entity.with_child((
+                MaterialNode(material.clone()),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.),
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+            ));
+        })
+    }
+
+    /// Create a notification whose message is localized from `key` and `args`
+    #[cfg(feature = "l10n")]
+    pub fn from_key(key: impl Into<String>, args: &[(&str, &str)]) -> Self {
+        NotiBoxEvent {
+            msg_key: Some(key.into()),
+            msg_args: args
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ..default()
+        }
+    }
+
+    /// Create a notification from a [`NotiTemplate`], replacing its `{name}` placeholders with `args`.
+    /// It is shown once the template is loaded.
+    #[cfg(feature = "template")]
+    pub fn from_template(template: Handle<NotiTemplate>, args: &[(&str, &str)]) -> Self {
+        NotiBoxEvent {
+            template: Some(NotiTemplateRef {
+                handle: template,
+                args: args
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+            }),
+            ..default()
+        }
+    }
 }
 
-#[derive(Component, Default)]
-#[require(Interaction)]
-struct NotiBox {
-    states: Vec<(AnimationState, Timer)>,
+/// Send and dismiss notifications, keeping track of their ids
+#[derive(SystemParam)]
+pub struct NotiBoxCommands<'w> {
+    notify: MessageWriter<'w, NotiBoxEvent>,
+    dismiss: MessageWriter<'w, DismissNoti>,
+    dismiss_all: MessageWriter<'w, DismissAllNotiBoxes>,
+    update: MessageWriter<'w, UpdateNotiBox>,
 }
 
-fn listen_event(mut commands: Commands, mut event: MessageReader<NotiBoxEvent>) {
-    for noti in event.read() {
-        let states = if noti.show_time > 0. {
-            vec![
-                (
-                    AnimationState::Start,
-                    Timer::from_seconds(DEFAULT_ANIMATION_DURATION, TimerMode::Once),
-                ),
-                (
-                    AnimationState::Middle,
-                    Timer::from_seconds(noti.show_time, TimerMode::Once),
-                ),
-                (
-                    AnimationState::End,
-                    Timer::from_seconds(DEFAULT_ANIMATION_DURATION, TimerMode::Once),
-                ),
-            ]
-        } else {
-            Vec::new()
-        };
+impl NotiBoxCommands<'_> {
+    /// Send a notification and return its id
+    pub fn send(&mut self, noti: impl Into<NotiBoxEvent>) -> NotiBoxId {
+        let mut noti = noti.into();
+        let id = *noti.id.get_or_insert_with(NotiBoxId::unique);
+        self.notify.write(noti);
+        id
+    }
 
-        let mut bg_color = noti.background_color.0.clone();
-        bg_color.set_alpha(0.4);
-        let border_color = BorderColor::from(bg_color);
-        let mut background_color = noti.background_color.0;
-        background_color.set_alpha(0.0);
-        let mut text_color = noti.text_color;
-        text_color.set_alpha(0.0);
-
-        commands.spawn((
-            NotiBox { states },
-            pos_to_style(&noti.pos),
-            BackgroundColor::from(background_color),
-            border_color,
-            Text::from(noti.msg.clone()),
-            noti.font.clone(),
-            TextColor::from(text_color),
-        ));
-    }
-}
-
-fn listen_click(mut commands: Commands, query: Query<(&Interaction, Entity), (Changed<Interaction>, With<NotiBox>)>) {
-    for (i, e) in query.iter() {
-        if *i == Interaction::Pressed {
-            commands.entity(e).despawn();
-        }
+    pub fn dismiss(&mut self, id: NotiBoxId) {
+        self.dismiss.write(DismissNoti(id));
+    }
+
+    pub fn update(&mut self, update: UpdateNotiBox) {
+        self.update.write(update);
+    }
+
+    pub fn dismiss_all(&mut self) {
+        self.dismiss_all.write(DismissAllNotiBoxes::default());
     }
 }
 
-fn countdown(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut NotiBox, &mut BackgroundColor, &mut TextColor)>,
-    time: Res<Time>,
-) {
-    for (e, mut noti_box, mut bg_color, mut text_color) in query.iter_mut() {
-        for (state, ref mut timer) in noti_box.states.iter_mut() {
-            if timer.is_finished() {
-                continue;
-            }
-            timer.tick(time.delta());
-            match state {
-                AnimationState::Start => {
-                    let alpha = timer.elapsed_secs() / timer.duration().as_secs_f32();
-                    bg_color.0.set_alpha(alpha);
-                    text_color.0.set_alpha(alpha);
-                }
-                AnimationState::Middle => {
-                    bg_color.0.set_alpha(1.);
-                    text_color.0.set_alpha(1.);
-                }
-                AnimationState::End => {
-                    let alpha = timer.remaining_secs() / timer.duration().as_secs_f32();
-                    bg_color.0.set_alpha(alpha);
-                    text_color.0.set_alpha(alpha);
+/// Look up and dismiss the notifications on screen
+#[derive(SystemParam)]
+pub struct NotiBoxes<'w, 's> {
+    commands: Commands<'w, 's>,
+    query: Query<'w, 's, (Entity, &'static NotiBoxId, &'static mut NotiBox)>,
+}
 
-                    if timer.just_finished() {
-                        commands.entity(e).despawn();
-                    }
-                }
-            }
-            break;
+impl NotiBoxes<'_, '_> {
+    /// Number of notifications on screen, not counting the ones going away
+    pub fn count(&self) -> usize {
+        self.query
+            .iter()
+            .filter(|(_, _, noti_box)| !noti_box.is_leaving())
+            .count()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, NotiBoxId, &NotiBox)> + '_ {
+        self.query.iter().map(|(e, id, noti_box)| (e, *id, noti_box))
+    }
+
+    pub fn get(&self, id: NotiBoxId) -> Option<(Entity, &NotiBox)> {
+        self.iter()
+            .find(|(_, noti_id, _)| *noti_id == id)
+            .map(|(e, _, noti_box)| (e, noti_box))
+    }
+
+    /// Fade out then despawn the notification with this id. Return `false` if it is not on screen.
+    pub fn dismiss(&mut self, id: NotiBoxId) -> bool {
+        let mut found = false;
+        for (e, noti_id, mut noti_box) in self.query.iter_mut().filter(|(_, noti_id, _)| **noti_id == id) {
+            dismiss_noti_box(&mut self.commands, e, *noti_id, &mut noti_box, DismissReason::Api);
+            found = true;
+        }
+        found
+    }
+
+    /// Fade out then despawn every notification on screen. Queued ones are left alone.
+    pub fn dismiss_all(&mut self) {
+        for (e, noti_id, mut noti_box) in self.query.iter_mut() {
+            dismiss_noti_box(&mut self.commands, e, *noti_id, &mut noti_box, DismissReason::Api);
         }
     }
 }
 
-fn pos_to_style(pos: &NotiPosition) -> Node {
-    let mut ret = Node {
-        width: Val::Percent(20.),
-        height: Val::Percent(20.),
-        margin: UiRect::all(Val::Px(5.)),
-        justify_content: JustifyContent::Center,
-        align_content: AlignContent::Center,
-        align_items: AlignItems::Center,
-        justify_items: JustifyItems::Center,
-        ..default()
-    };
+/// Where a notification is in its lifetime, see [`NotiBox::state`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NotiBoxState {
+    /// Playing its entrance animation
+    Entering,
+    /// Fully shown, counting down its show time
+    Shown,
+    /// Fading out before being despawned
+    Leaving,
+}
 
-    match pos {
-        NotiPosition::TopLeft => {
-            ret.justify_self = JustifySelf::Start;
-            ret.align_self = AlignSelf::FlexStart;
+/// A notification on screen. Query it with [`NotiBoxId`] or use [`NotiBoxes`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Interaction)]
+pub struct NotiBox {
+    states: Vec<(AnimationState, Timer)>,
+    msg: String,
+    pos: NotiPosition,
+    priority: NotiPriority,
+    /// Time since startup when it was spawned
+    shown_at: Duration,
+    /// How many times the same notification was sent while this one is visible
+    repeat: u32,
+    /// Text of the message and spans that duplicates have, `None` if they are not merged into this one
+    duplicate_key: Option<Vec<String>>,
+    tag: Option<String>,
+    channel: Option<String>,
+    close_button: bool,
+    interactive: bool,
+    /// Lines of the message shown until it is clicked, `None` once expanded
+    max_lines: Option<usize>,
+    /// Whether the message is cut at `max_lines`, so that a click expands it
+    truncated: bool,
+    /// Characters typed per second, `0.` if the message is shown at once
+    typewriter: f32,
+    /// Characters of the message typed so far
+    typed: f32,
+    /// Set once the notification is going away before its show time runs out
+    dismiss_reason: Option<DismissReason>,
+    entrance: NotiAnimation,
+    exit: NotiAnimation,
+    easing: EaseFunction,
+    #[reflect(ignore)]
+    animator: Option<Arc<dyn NotiAnimator>>,
+    /// How far the current animation state is, from 0 (hidden) to 1 (fully shown)
+    progress: f32,
+    /// Fill of the bar of a [`NotiBoxEvent::progress`] notification
+    fill: Option<f32>,
+    /// Colors at full opacity, faded in and out by `countdown`
+    background: Color,
+    border: Color,
+    /// Colors at full opacity while the cursor is over an interactive notification
+    hover_background: Color,
+    hover_border: Color,
+    /// Whether a `bevy_picking` pointer is over it, besides its [`Interaction`]
+    pointer_over: bool,
+    /// Whether its colors were last set to the hover ones
+    highlighted: bool,
+    /// Cursor x position where a swipe would put the notification in place
+    drag_start: Option<f32>,
+    /// Horizontal swipe offset, in percent of its width
+    drag: f32,
+    /// Direction it flies off to once swiped away, `0.` otherwise
+    fling: f32,
+    /// Place given by the layout of its stack, in logical pixels
+    slot: Option<Vec2>,
+    /// Offset from `slot` while sliding to it after the stack changed, in logical pixels
+    reflow: Vec2,
+    /// Whether its entity can go back to the pool once it is gone
+    recyclable: bool,
+}
+
+impl NotiBox {
+    /// Part of the message typed so far, see [`NotiBoxEvent::typewriter`]
+    fn typed_msg(&self) -> &str {
+        match self.msg.char_indices().nth(self.typed as usize) {
+            Some((end, _)) if self.typewriter > 0. => &self.msg[..end],
+            _ => &self.msg,
         }
-        NotiPosition::TopMid => {
-            ret.justify_self = JustifySelf::Center;
-            ret.align_self = AlignSelf::FlexStart;
+    }
+
+    fn is_typing(&self) -> bool {
+        self.typed_msg().len() < self.msg.len()
+    }
+
+    /// Skip straight to the fade-out, starting from the current progress.
+    /// Return `false` if there is no fade-out to play.
+    fn dismiss(&mut self, reason: DismissReason) -> bool {
+        self.dismiss_reason = Some(reason);
+        let progress = self.progress;
+        let mut has_end = false;
+        for (state, timer) in self.states.iter_mut() {
+            if *state == AnimationState::End {
+                let duration = timer.duration();
+                timer.set_elapsed(duration.mul_f32(1. - progress));
+                has_end = true;
+            } else {
+                // Sticky notifications have a paused show timer
+                timer.unpause();
+                timer.finish();
+            }
         }
-        NotiPosition::TopRight => {
-            ret.justify_self = JustifySelf::End;
-            ret.align_self = AlignSelf::FlexStart;
+        has_end
+    }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn pos(&self) -> NotiPosition {
+        self.pos
+    }
+
+    pub fn priority(&self) -> NotiPriority {
+        self.priority
+    }
+
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    /// Time since startup when it was spawned
+    pub fn shown_at(&self) -> Duration {
+        self.shown_at
+    }
+
+    /// How many times it was sent while visible, `1` if it was not repeated
+    pub fn repeat_count(&self) -> u32 {
+        self.repeat
+    }
+
+    pub fn state(&self) -> NotiBoxState {
+        if self.is_leaving() {
+            return NotiBoxState::Leaving;
         }
-        NotiPosition::MidLeft => {
-            ret.justify_self = JustifySelf::Start;
-            ret.align_self = AlignSelf::Center;
+        match self.current_state() {
+            Some(AnimationState::Start) => NotiBoxState::Entering,
+            Some(AnimationState::Middle) => NotiBoxState::Shown,
+            _ => NotiBoxState::Leaving,
         }
-        NotiPosition::Center => {
-            ret.justify_self = JustifySelf::Center;
-            ret.align_self = AlignSelf::Center;
+    }
+
+    /// Show time left before it starts fading out. `None` if it is sticky.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        if self.is_leaving() {
+            return Some(Duration::ZERO);
+        }
+        self.states
+            .iter()
+            .find(|(state, _)| *state == AnimationState::Middle)
+            .and_then(|(_, timer)| (!timer.is_paused()).then(|| timer.remaining()))
+    }
+
+    /// Why it is going away, `None` while it is not or if its show time ran out
+    pub fn dismiss_reason(&self) -> Option<DismissReason> {
+        self.dismiss_reason
+    }
+
+    fn current_state(&self) -> Option<&AnimationState> {
+        self.states
+            .iter()
+            .find(|(_, timer)| !timer.is_finished())
+            .map(|(state, _)| state)
+    }
+
+    /// `true` once the notification started fading out
+    pub fn is_leaving(&self) -> bool {
+        self.dismiss_reason.is_some() || self.current_state().is_none_or(|state| *state == AnimationState::End)
+    }
+
+    /// Count one more repeat and restart the show time
+    fn repeat(&mut self) {
+        self.repeat += 1;
+        self.restart(None);
+    }
+
+    /// Stop the show time, so it stays until clicked or dismissed
+    fn pin(&mut self) {
+        for (state, timer) in self.states.iter_mut() {
+            if *state == AnimationState::Middle {
+                timer.pause();
+            }
+        }
+    }
+
+    /// Restart the show time, changing its duration if `show_time` is set
+    fn restart(&mut self, show_time: Option<f32>) {
+        for (state, timer) in self.states.iter_mut() {
+            if *state == AnimationState::Middle {
+                if let Some(show_time) = show_time {
+                    *timer = show_timer(show_time);
+                } else {
+                    timer.reset();
+                }
+            }
+        }
+    }
+
+    /// Fraction of the show time left, `1.` until the notification is fully shown
+    fn remaining_fraction(&self) -> f32 {
+        for (state, timer) in self.states.iter() {
+            if timer.is_finished() {
+                continue;
+            }
+            return match state {
+                AnimationState::Start => 1.,
+                AnimationState::Middle if timer.is_paused() => 1.,
+                AnimationState::Middle => timer.fraction_remaining(),
+                AnimationState::End => 0.,
+            };
+        }
+        0.
+    }
+
+    /// Apply `alpha` to the notification and its parts, with the hover colors if `highlighted`
+    fn set_alpha(
+        &self,
+        alpha: f32,
+        highlighted: bool,
+        bg_color: &mut BackgroundColor,
+        border_color: &mut BorderColor,
+        parts: impl Iterator<Item = Entity>,
+        fades: &mut Query<NotiFadeQuery, Without<NotiBox>>,
+    ) {
+        let (background, border) = if highlighted {
+            (self.hover_background, self.hover_border)
+        } else {
+            (self.background, self.border)
+        };
+        bg_color.0 = background.with_alpha(background.alpha() * alpha);
+        *border_color = BorderColor::all(border.with_alpha(border.alpha() * alpha));
+        for part in parts {
+            if let Ok((fade, text_color, image, part_bg_color)) = fades.get_mut(part) {
+                let foreground = fade.foreground.with_alpha(fade.foreground.alpha() * alpha);
+                if let Some(mut text_color) = text_color {
+                    text_color.0 = foreground;
+                }
+                if let Some(mut image) = image {
+                    image.color = foreground;
+                }
+                if let Some(mut part_bg_color) = part_bg_color.filter(|_| fade.background.alpha() > 0.) {
+                    part_bg_color.0 = fade.background.with_alpha(fade.background.alpha() * alpha);
+                }
+            }
+        }
+    }
+}
+
+/// Colors at full opacity of a part (text, icon, button...) of a [`NotiBox`], faded along with it
+#[derive(Component, Clone, Copy)]
+struct NotiFade {
+    foreground: Color,
+    background: Color,
+}
+
+impl NotiFade {
+    fn new(foreground: Color) -> Self {
+        Self {
+            foreground,
+            background: Color::NONE,
+        }
+    }
+}
+
+type NotiFadeQuery = (
+    &'static NotiFade,
+    Option<&'static mut TextColor>,
+    Option<&'static mut ImageNode>,
+    Option<&'static mut BackgroundColor>,
+);
+
+/// Text child of a [`NotiBox`]
+#[derive(Component)]
+struct NotiBoxText {
+    noti_box: Entity,
+}
+
+/// Text of a [`NotiBoxEvent::marquee`] notification, in a node clipping it
+#[derive(Component)]
+struct NotiMarquee {
+    speed: f32,
+    /// Left offset of the text, in logical pixels
+    offset: f32,
+}
+
+/// Close button child of a [`NotiBox`]
+#[derive(Component)]
+#[require(Button)]
+struct NotiCloseButton {
+    noti_box: Entity,
+}
+
+/// Copy button child of a [`NotiBox`]
+#[derive(Component)]
+#[require(Button)]
+struct NotiCopyButton {
+    noti_box: Entity,
+}
+
+/// Action button child of a [`NotiBox`]
+#[derive(Component)]
+#[require(Button)]
+struct NotiActionButton {
+    noti_box: Entity,
+    action: NotiAction,
+}
+
+/// Text child of a [`NotiBox`] showing how many times it was repeated
+#[derive(Component)]
+struct NotiRepeatCounter {
+    noti_box: Entity,
+}
+
+/// Bar child of a [`NotiBox`] showing the remaining show time
+#[derive(Component)]
+struct NotiProgressBar {
+    noti_box: Entity,
+}
+
+/// Filled part of the bar of a [`NotiBoxEvent::progress`] notification
+#[derive(Component)]
+struct NotiProgressFill {
+    noti_box: Entity,
+}
+
+/// Percentage shown next to the bar of a [`NotiBoxEvent::progress`] notification
+#[derive(Component)]
+struct NotiProgressLabel {
+    noti_box: Entity,
+}
+
+/// Spinning child of a [`NotiBoxEvent::spinner`] notification
+#[derive(Component)]
+struct NotiSpinner {
+    noti_box: Entity,
+    color: Color,
+}
+
+/// Full-screen node behind a modal [`NotiBox`], faded with it
+#[derive(Component)]
+struct NotiModalOverlay {
+    noti_box: Entity,
+    color: Color,
+}
+
+/// Layout node holding every notification shown at one position, so they stack instead of overlapping.
+#[derive(Component)]
+struct NotiContainer {
+    pos: NotiPosition,
+    /// UI node the container is spawned under, root level if `None`
+    parent: Option<Entity>,
+    /// Camera a root level container is rendered with, the default UI camera if `None`
+    camera: Option<Entity>,
+}
+
+/// Notifications waiting for a free slot
+#[derive(Resource, Default)]
+struct NotiQueue(VecDeque<NotiBoxEvent>);
+
+impl NotiQueue {
+    /// Add behind every queued notification of the same or higher priority
+    fn push(&mut self, noti: NotiBoxEvent) {
+        let index = self
+            .0
+            .iter()
+            .position(|queued| queued.priority < noti.priority)
+            .unwrap_or(self.0.len());
+        self.0.insert(index, noti);
+    }
+
+    /// Remove the queued notifications matching `filter`, telling they will not be shown
+    fn remove(&mut self, dropped: &mut MessageWriter<NotiBoxDropped>, mut filter: impl FnMut(&NotiBoxEvent) -> bool) {
+        self.0.retain(|noti| {
+            if !filter(noti) {
+                return true;
+            }
+            if let Some(id) = noti.id {
+                dropped.write(NotiBoxDropped { id });
+            }
+            false
+        });
+    }
+}
+
+/// Clock selected by [`NotiBoxSettings::real_time`]
+#[derive(SystemParam)]
+struct NotiClock<'w> {
+    settings: Res<'w, NotiBoxSettings>,
+    virtual_time: Res<'w, Time>,
+    real_time: Res<'w, Time<Real>>,
+}
+
+impl NotiClock<'_> {
+    fn delta(&self) -> Duration {
+        if self.settings.real_time {
+            self.real_time.delta()
+        } else {
+            self.virtual_time.delta()
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        if self.settings.real_time {
+            self.real_time.elapsed()
+        } else {
+            self.virtual_time.elapsed()
+        }
+    }
+}
+
+/// Notifications received, once their template is loaded, their delay is over
+/// and [`NotiBoxSettings::do_not_disturb`] is `false`
+#[derive(SystemParam)]
+struct NotiInbox<'w, 's> {
+    event: MessageReader<'w, 's, NotiBoxEvent>,
+    settings: Res<'w, NotiBoxSettings>,
+    windows: Query<'w, 's, (Entity, &'static Window), With<PrimaryWindow>>,
+    occlusion: MessageReader<'w, 's, WindowOccluded>,
+    /// Whether the primary window is hidden, e.g. in a background browser tab
+    occluded: Local<'s, bool>,
+    native: MessageWriter<'w, NotiNativeRequest>,
+    dropped: MessageWriter<'w, NotiBoxDropped>,
+    #[cfg(feature = "template")]
+    templates: template::NotiTemplates<'w, 's>,
+    /// Delayed notifications, with the time they are due
+    delayed: Local<'s, Vec<(Duration, NotiBoxEvent)>>,
+    /// Notifications held back by do not disturb
+    deferred: Local<'s, Vec<NotiBoxEvent>>,
+}
+
+impl NotiInbox<'_, '_> {
+    fn read(&mut self, now: Duration) -> Vec<NotiBoxEvent> {
+        for occlusion in self.occlusion.read() {
+            if self.windows.contains(occlusion.window) {
+                *self.occluded = occlusion.occluded;
+            }
+        }
+
+        let mut ready = Vec::new();
+        self.delayed.retain(|(due, noti)| {
+            if *due > now {
+                return true;
+            }
+            ready.push(noti.clone());
+            false
+        });
+
+        let incoming = self.event.read().cloned();
+        #[cfg(feature = "template")]
+        let incoming = self.templates.resolve(incoming);
+        for noti in incoming {
+            if noti.delay > 0. {
+                // Too far ahead to ever be due
+                let due = Duration::try_from_secs_f32(noti.delay)
+                    .ok()
+                    .and_then(|delay| now.checked_add(delay));
+                let Some(due) = due else {
+                    if let Some(id) = noti.id {
+                        self.dropped.write(NotiBoxDropped { id });
+                    }
+                    continue;
+                };
+                self.delayed.push((due, NotiBoxEvent { delay: 0., ..noti }));
+            } else {
+                ready.push(noti);
+            }
+        }
+
+        if self.settings.do_not_disturb {
+            self.deferred.append(&mut ready);
+            return ready;
+        }
+        let summarize_over = self.settings.summarize_over;
+        if summarize_over != 0 && self.deferred.len() > summarize_over {
+            let count = self.deferred.len();
+            for noti in std::mem::take(&mut *self.deferred) {
+                self.discard(&noti);
+            }
+            ready.insert(
+                0,
+                NotiBoxEvent {
+                    msg: self.settings.summary_msg.replace("{count}", &count.to_string()),
+                    ..default()
+                },
+            );
+        } else {
+            ready.splice(0..0, self.deferred.drain(..));
+        }
+        ready
+    }
+
+    /// Send `noti` to the OS instead of showing it while the window is unfocused, see
+    /// [`NotiBoxSettings::native_when_unfocused`]
+    fn hand_off(&mut self, noti: &NotiBoxEvent) -> bool {
+        let unfocused = *self.occluded || self.windows.iter().any(|(_, window)| !window.focused);
+        if !self.settings.native_when_unfocused || !unfocused {
+            return false;
+        }
+        self.native.write(noti.into());
+        self.discard(noti);
+        true
+    }
+
+    /// Tell that `noti` will not be shown
+    fn discard(&mut self, noti: &NotiBoxEvent) {
+        if let Some(id) = noti.id {
+            self.dropped.write(NotiBoxDropped { id });
+        }
+    }
+}
+
+/// Queue a triggered notification like a sent one
+fn forward_triggered(noti: On<NotiBoxEvent>, mut notis: MessageWriter<NotiBoxEvent>) {
+    notis.write(noti.event().clone());
+}
+
+fn listen_event(
+    mut commands: Commands,
+    mut inbox: NotiInbox,
+    mut queue: ResMut<NotiQueue>,
+    settings: Res<NotiBoxSettings>,
+    theme: Res<NotiBoxTheme>,
+    time: NotiClock,
+    containers: Query<(Entity, &NotiContainer)>,
+    cameras: Query<(Entity, &Camera, Option<&RenderLayers>)>,
+    mut noti_boxes: Query<(Entity, &NotiBoxId, &mut NotiBox), Without<world::NotiWorldBox>>,
+    mut limiter: Local<RateLimiter>,
+    mut history: ResMut<NotiHistory>,
+    channels: Res<NotiChannels>,
+    asset_server: Option<Res<AssetServer>>,
+    mut pool: pool::NotiPoolParam,
+    #[cfg(feature = "l10n")] localizer: Option<Res<NotiLocalizer>>,
+) {
+    // Visible notifications that a duplicate can be merged into
+    let mut shown: HashMap<(Vec<String>, NotiPosition), Entity> = HashMap::default();
+    if settings.deduplicate {
+        for (e, _, noti_box) in noti_boxes.iter().filter(|(_, _, noti_box)| !noti_box.is_leaving()) {
+            if let Some(key) = &noti_box.duplicate_key {
+                shown.insert((key.clone(), noti_box.pos), e);
+            }
+        }
+    }
+    let repeat =
+        |commands: &mut Commands, shown: &HashMap<(Vec<String>, NotiPosition), Entity>, noti: &NotiBoxEvent| {
+            let Some(e) = noti.duplicate_key().and_then(|key| shown.get(&(key, noti.pos))) else {
+                return false;
+            };
+            commands
+                .entity(*e)
+                .entry::<NotiBox>()
+                .and_modify(|mut noti_box| noti_box.repeat());
+            true
+        };
+
+    for mut noti in inbox.read(time.elapsed()) {
+        #[cfg(feature = "l10n")]
+        l10n::localize(&mut noti, localizer.as_deref());
+        let channel = channels.of(&noti);
+        // Whether to record a notification that is not shown
+        let record_hidden = if settings.enabled {
+            channel
+                .filter(|channel| channel.muted)
+                .map(|channel| channel.record_muted)
+        } else {
+            Some(settings.record_disabled)
+        };
+        if let Some(record) = record_hidden {
+            if record {
+                history.push(NotiRecord::new(&noti, time.elapsed()));
+            }
+            inbox.discard(&noti);
+            continue;
+        }
+        if let Some(pos) = channel.and_then(|channel| channel.pos) {
+            if noti.pos == NotiPosition::default() {
+                noti.pos = pos;
+            }
+        }
+        if !limiter.allow(
+            &noti.msg,
+            time.elapsed(),
+            settings.max_per_second,
+            settings.repeat_interval,
+        ) {
+            inbox.discard(&noti);
+            continue;
+        }
+        history.push(NotiRecord::new(&noti, time.elapsed()));
+        if repeat(&mut commands, &shown, &noti) {
+            inbox.discard(&noti);
+        } else if !inbox.hand_off(&noti) {
+            queue.push(noti);
+        }
+    }
+
+    let mut visible = noti_boxes
+        .iter()
+        .filter(|(_, _, noti_box)| !noti_box.is_leaving())
+        .count();
+    let mut container_map: HashMap<(Option<Entity>, Option<Entity>, NotiPosition), Entity> = containers
+        .iter()
+        .map(|(e, c)| ((c.parent, c.camera, c.pos), e))
+        .collect();
+
+    // Notifications waiting for room at their position or in their channel
+    let mut waiting = VecDeque::new();
+    // Notifications spawned below with their position and channel, not in `noti_boxes` yet
+    let mut spawned: Vec<(Entity, NotiBoxId, NotiPosition, Option<String>)> = Vec::new();
+    while let Some(priority) = queue.0.front().map(|noti| noti.priority) {
+        if settings.max_visible != 0 && visible >= settings.max_visible {
+            if priority < NotiPriority::High {
+                break;
+            }
+            let oldest = noti_boxes
+                .iter_mut()
+                .filter(|(_, _, noti_box)| !noti_box.is_leaving() && noti_box.priority < priority)
+                .min_by_key(|(_, _, noti_box)| noti_box.shown_at);
+            let Some((e, id, mut noti_box)) = oldest else {
+                break;
+            };
+            dismiss_noti_box(&mut commands, e, *id, &mut noti_box, DismissReason::Evicted);
+            visible -= 1;
+        }
+
+        let Some(mut noti) = queue.0.pop_front() else {
+            break;
+        };
+        if repeat(&mut commands, &shown, &noti) {
+            inbox.discard(&noti);
+            continue;
+        }
+        let channel = channels.of(&noti);
+        let mut overflowed = None;
+        // Limit of the position, then of the channel
+        let limits = [
+            settings.position_limits.get(&noti.pos).map(|limit| (*limit, true)),
+            channel.map(|channel| (NotiLimit::new(channel.max_visible, channel.overflow), false)),
+        ];
+        for (limit, by_pos) in limits.into_iter().flatten() {
+            if limit.max_visible == 0 {
+                continue;
+            }
+            let same_place = |pos: NotiPosition, channel: &Option<String>| {
+                if by_pos {
+                    pos == noti.pos
+                } else {
+                    *channel == noti.channel
+                }
+            };
+            let mut same: Vec<_> = noti_boxes
+                .iter_mut()
+                .filter(|(_, _, noti_box)| !noti_box.is_leaving() && same_place(noti_box.pos, &noti_box.channel))
+                .collect();
+            let spawned_same = spawned
+                .iter()
+                .filter(|(_, _, pos, channel)| same_place(*pos, channel))
+                .count();
+            let excess = (same.len() + spawned_same + 1).saturating_sub(limit.max_visible);
+            if excess == 0 {
+                continue;
+            }
+            if limit.overflow != NotiOverflow::DropOldest {
+                overflowed = Some(limit.overflow);
+                break;
+            }
+            same.sort_by_key(|(_, _, noti_box)| noti_box.shown_at);
+            for (e, id, noti_box) in same.iter_mut().take(excess) {
+                dismiss_noti_box(&mut commands, *e, **id, noti_box, DismissReason::Evicted);
+                visible -= 1;
+            }
+            // Then the ones spawned earlier in this frame, which are newer than any on screen
+            let mut excess = excess.saturating_sub(same.len());
+            spawned.retain(|(e, id, pos, channel)| {
+                if excess == 0 || !same_place(*pos, channel) {
+                    return true;
+                }
+                despawn_noti_box(&mut commands, *e, *id, DismissReason::Evicted);
+                shown.retain(|_, shown| shown != e);
+                excess -= 1;
+                visible -= 1;
+                false
+            });
+        }
+        match overflowed {
+            Some(NotiOverflow::Queue) => {
+                waiting.push_back(noti);
+                continue;
+            }
+            Some(_) => {
+                inbox.discard(&noti);
+                continue;
+            }
+            None => {}
+        }
+        let theme = channel.and_then(|channel| channel.theme.as_ref()).unwrap_or(&theme);
+        theme.apply(&mut noti);
+        noti.load_font(asset_server.as_deref());
+        let parent = noti.parent.or(settings.parent);
+        // Only root level UI nodes choose their camera
+        let camera = noti
+            .camera
+            .or_else(|| {
+                noti.render_layers
+                    .as_ref()
+                    .and_then(|layers| layer_camera(&cameras, layers))
+            })
+            .or(settings.camera)
+            .filter(|_| parent.is_none());
+        let container = *container_map.entry((parent, camera, noti.pos)).or_insert_with(|| {
+            let mut container = commands.spawn((
+                NotiContainer {
+                    pos: noti.pos,
+                    parent,
+                    camera,
+                },
+                pos_to_style(&noti.pos, &settings),
+            ));
+            if let Some(parent) = parent {
+                container.insert(ChildOf(parent));
+            }
+            if let Some(camera) = camera {
+                container.insert(UiTargetCamera(camera));
+            }
+            container.id()
+        });
+        let duplicate_key = noti.duplicate_key().filter(|_| settings.deduplicate);
+        let id = *noti.id.get_or_insert_with(NotiBoxId::unique);
+        let e = spawn_noti_box(
+            &mut commands,
+            pool.take(),
+            container,
+            &noti,
+            &settings,
+            theme,
+            time.elapsed(),
+        );
+        if noti.modal {
+            let mut overlay = commands.spawn((
+                NotiModalOverlay {
+                    noti_box: e,
+                    color: theme.modal_color,
+                },
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                FocusPolicy::Block,
+                GlobalZIndex(noti.z_index.unwrap_or(theme.z_index) - 1),
+            ));
+            if let Some(camera) = camera {
+                overlay.insert(UiTargetCamera(camera));
+            }
+        }
+        if let Some(key) = duplicate_key {
+            shown.insert((key.clone(), noti.pos), e);
+            commands
+                .entity(e)
+                .entry::<NotiBox>()
+                .and_modify(|mut noti_box| noti_box.duplicate_key = Some(key));
+        }
+        spawned.push((e, id, noti.pos, noti.channel.clone()));
+        visible += 1;
+    }
+    for noti in waiting.into_iter().rev() {
+        queue.0.push_front(noti);
+    }
+}
+
+/// Active camera rendering one of `layers`, with the highest order
+fn layer_camera(cameras: &Query<(Entity, &Camera, Option<&RenderLayers>)>, layers: &RenderLayers) -> Option<Entity> {
+    cameras
+        .iter()
+        .filter(|(_, camera, camera_layers)| {
+            camera.is_active && camera_layers.cloned().unwrap_or_default().intersects(layers)
+        })
+        .max_by_key(|(_, camera, _)| camera.order)
+        .map(|(e, _, _)| e)
+}
+
+/// Spawn the notification, on `pooled` if given
+fn spawn_noti_box(
+    commands: &mut Commands,
+    pooled: Option<Entity>,
+    container: Entity,
+    noti: &NotiBoxEvent,
+    settings: &NotiBoxSettings,
+    theme: &NotiBoxTheme,
+    now: Duration,
+) -> Entity {
+    // Fixed sizes follow the theme scale like the rest of the notification
+    let px = |px: f32| Val::Px(px * theme.scale);
+    let reduce_motion = |animation: NotiAnimation| {
+        if settings.reduced_motion && animation != NotiAnimation::None {
+            NotiAnimation::Fade
+        } else {
+            animation
+        }
+    };
+    let entrance = reduce_motion(noti.entrance);
+    let exit = reduce_motion(noti.exit.unwrap_or(noti.entrance));
+    let duration = |animation: NotiAnimation, duration: f32| match animation {
+        NotiAnimation::None => 0.,
+        _ if settings.reduced_motion => duration.min(REDUCED_MOTION_FADE),
+        _ => duration,
+    };
+    let fade_in = duration(entrance, noti.fade_in.unwrap_or(theme.fade_in));
+    let fade_out = duration(exit, noti.fade_out.unwrap_or(theme.fade_out));
+    let show_time = match noti.progress {
+        Some(progress) if progress < 1. => 0.,
+        Some(_) => settings.progress_linger.max(f32::EPSILON),
+        None if noti.spinner => 0.,
+        None => noti.show_time,
+    };
+    let states = vec![
+        (AnimationState::Start, fade_timer(fade_in)),
+        (AnimationState::Middle, show_timer(show_time)),
+        (AnimationState::End, fade_timer(fade_out)),
+    ];
+
+    let background = noti.background_color.0;
+    let border = noti.border_color.unwrap_or(background.with_alpha(0.4));
+    let transparent = |c: Color| c.with_alpha(0.);
+
+    #[cfg(feature = "audio")]
+    if let Some(sound) = noti.show_sound.clone() {
+        audio::play_sound(commands, sound);
+    }
+
+    let bundle = (
+        NotiBox {
+            states,
+            msg: noti.msg.clone(),
+            pos: noti.pos,
+            priority: noti.priority,
+            shown_at: now,
+            repeat: 1,
+            duplicate_key: None,
+            tag: noti.tag.clone(),
+            channel: noti.channel.clone(),
+            close_button: noti.close_button,
+            interactive: noti.interactive,
+            max_lines: noti.max_lines,
+            truncated: false,
+            typewriter: noti.typewriter,
+            typed: 0.,
+            dismiss_reason: None,
+            entrance,
+            exit,
+            easing: noti.easing,
+            animator: noti.animator.clone(),
+            progress: 0.,
+            fill: noti.progress,
+            background,
+            border,
+            hover_background: background.lighter(theme.hover_lighten),
+            hover_border: theme.hover_border_color.unwrap_or(border),
+            pointer_over: false,
+            highlighted: false,
+            drag_start: None,
+            drag: 0.,
+            fling: 0.,
+            slot: None,
+            reflow: Vec2::ZERO,
+            recyclable: noti.extras.0.is_empty(),
+        },
+        noti.id.unwrap_or_else(NotiBoxId::unique),
+        box_style(noti, theme),
+        noti.border_radius.unwrap_or(theme.border_radius),
+        UiTransform {
+            translation: entrance.translation(noti.pos, 0.),
+            scale: entrance.scale(0.),
+            ..default()
+        },
+        if noti.interactive {
+            FocusPolicy::Block
+        } else {
+            FocusPolicy::Pass
+        },
+        GlobalZIndex(noti.z_index.unwrap_or(theme.z_index)),
+        AccessibilityNode(alert_node(&noti.msg)),
+        ChildOf(container),
+        BackgroundColor::from(transparent(background)),
+        BorderColor::all(transparent(border)),
+    );
+    let mut entity = match pooled {
+        Some(e) => {
+            let mut entity = commands.entity(e);
+            entity.insert(bundle).remove::<pool::NotiPooled>();
+            entity
+        }
+        None => commands.spawn(bundle),
+    };
+
+    #[cfg(feature = "audio")]
+    if let Some(sound) = noti.dismiss_sound.clone() {
+        entity.insert(audio::NotiDismissSound(sound));
+    }
+    for extra in noti.extras.0.iter() {
+        extra(&mut entity);
+    }
+
+    entity.with_children(|parent| {
+        let noti_box = parent.target_entity();
+        if let Some(image) = &noti.background_image {
+            parent.spawn((
+                NotiFade::new(image.color),
+                ImageNode {
+                    color: transparent(image.color),
+                    ..image.clone()
+                },
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.),
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    ..default()
+                },
+            ));
+        }
+        parent
+            .spawn(Node {
+                align_items: AlignItems::Center,
+                ..default()
+            })
+            .with_children(|content| {
+                if noti.spinner {
+                    content.spawn((
+                        NotiSpinner {
+                            noti_box,
+                            color: noti.text_color,
+                        },
+                        Node {
+                            width: px(SPINNER_SIZE),
+                            height: px(SPINNER_SIZE),
+                            margin: UiRect::right(px(5.)),
+                            border: UiRect::all(px(3.)),
+                            ..default()
+                        },
+                        BorderRadius::MAX,
+                        BorderColor::all(Color::NONE),
+                    ));
+                }
+                if let Some(icon) = noti.icon.as_ref().or(settings.level_icons.get(&noti.level)) {
+                    content.spawn((
+                        NotiFade::new(Color::WHITE),
+                        ImageNode::new(icon.clone()).with_color(transparent(Color::WHITE)),
+                        Node {
+                            width: px(ICON_SIZE),
+                            height: px(ICON_SIZE),
+                            margin: UiRect::right(px(5.)),
+                            ..default()
+                        },
+                    ));
+                }
+                let linebreak = if noti.marquee > 0. {
+                    LineBreak::NoWrap
+                } else {
+                    noti.linebreak.unwrap_or(theme.linebreak)
+                };
+                let text = (
+                    NotiBoxText { noti_box },
+                    NotiFade::new(noti.text_color),
+                    // Filled by `truncate_text` while it is typed
+                    Text::from(if noti.typewriter > 0. {
+                        String::new()
+                    } else {
+                        noti.msg.clone()
+                    }),
+                    TextLayout::new(noti.justify.unwrap_or(theme.justify), linebreak),
+                    noti.font.clone(),
+                    TextColor::from(transparent(noti.text_color)),
+                );
+                let spans = |text: &mut ChildSpawnerCommands| {
+                    for span in noti.spans.iter() {
+                        let color = span.color.unwrap_or(noti.text_color);
+                        text.spawn((
+                            NotiFade::new(color),
+                            TextSpan::new(span.text.clone()),
+                            span.font.clone().unwrap_or_else(|| noti.font.clone()),
+                            TextColor::from(transparent(color)),
+                        ));
+                    }
+                };
+                if noti.marquee > 0. {
+                    content
+                        .spawn(Node {
+                            overflow: Overflow::clip_x(),
+                            min_width: Val::Px(0.),
+                            ..default()
+                        })
+                        .with_children(|clip| {
+                            clip.spawn((
+                                text,
+                                NotiMarquee {
+                                    speed: noti.marquee,
+                                    offset: 0.,
+                                },
+                            ))
+                            .with_children(spans);
+                        });
+                } else {
+                    content.spawn(text).with_children(spans);
+                }
+                if settings.deduplicate {
+                    content.spawn((
+                        NotiRepeatCounter { noti_box },
+                        NotiFade::new(noti.text_color),
+                        Node {
+                            margin: UiRect::left(px(5.)),
+                            ..default()
+                        },
+                        Text::default(),
+                        noti.font.clone(),
+                        TextColor::from(transparent(noti.text_color)),
+                    ));
+                }
+            });
+
+        if let Some(progress) = noti.progress {
+            parent
+                .spawn(Node {
+                    margin: UiRect::top(px(5.)),
+                    column_gap: px(5.),
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        NotiFade {
+                            foreground: Color::NONE,
+                            background: noti.text_color.with_alpha(0.2),
+                        },
+                        Node {
+                            min_width: px(120.),
+                            height: px(6.),
+                            flex_grow: 1.,
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::NONE),
+                    ))
+                    .with_child((
+                        NotiProgressFill { noti_box },
+                        NotiFade {
+                            foreground: Color::NONE,
+                            background: noti.text_color.with_alpha(0.8),
+                        },
+                        Node {
+                            width: Val::Percent(progress.clamp(0., 1.) * 100.),
+                            height: Val::Percent(100.),
+                            ..default()
+                        },
+                        BackgroundColor::from(Color::NONE),
+                    ));
+                    row.spawn((
+                        NotiProgressLabel { noti_box },
+                        NotiFade::new(noti.text_color),
+                        Text::from(progress_label(progress)),
+                        noti.font.clone(),
+                        TextColor::from(transparent(noti.text_color)),
+                    ));
+                });
+        }
+
+        if noti.interactive && !noti.actions.is_empty() {
+            parent
+                .spawn(Node {
+                    margin: UiRect::top(px(5.)),
+                    column_gap: px(5.),
+                    ..default()
+                })
+                .with_children(|row| {
+                    for action in noti.actions.iter().take(MAX_ACTIONS) {
+                        row.spawn((
+                            NotiActionButton {
+                                noti_box,
+                                action: action.clone(),
+                            },
+                            NotiFade {
+                                foreground: noti.text_color,
+                                background: noti.text_color.with_alpha(0.15),
+                            },
+                            Node {
+                                padding: UiRect::axes(px(8.), px(2.)),
+                                ..default()
+                            },
+                            BackgroundColor::from(Color::NONE),
+                            Text::from(action.label.clone()),
+                            noti.font.clone(),
+                            TextColor::from(transparent(noti.text_color)),
+                        ));
+                    }
+                });
+        }
+
+        if noti.progress_bar && noti.show_time > 0. {
+            parent.spawn((
+                NotiProgressBar { noti_box },
+                NotiFade {
+                    foreground: Color::NONE,
+                    background: noti.text_color.with_alpha(0.6),
+                },
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.),
+                    bottom: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    height: px(3.),
+                    ..default()
+                },
+                BackgroundColor::from(Color::NONE),
+            ));
+        }
+
+        if noti.interactive && noti.close_button {
+            parent.spawn((
+                NotiCloseButton { noti_box },
+                NotiFade::new(noti.text_color),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: px(2.),
+                    right: px(5.),
+                    ..default()
+                },
+                Text::from("x"),
+                noti.font.clone(),
+                TextColor::from(transparent(noti.text_color)),
+            ));
+        }
+
+        if noti.interactive && noti.copy_button {
+            // Left of the close button
+            let right = if noti.close_button { 20. } else { 5. };
+            parent.spawn((
+                NotiCopyButton { noti_box },
+                NotiFade::new(noti.text_color),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: px(2.),
+                    right: px(right),
+                    ..default()
+                },
+                Text::from("Copy"),
+                noti.font.clone(),
+                TextColor::from(transparent(noti.text_color)),
+            ));
+        }
+    });
+    // Let the pointer reach what is underneath, through the text and images inside too
+    #[cfg(feature = "picking")]
+    if !noti.interactive {
+        entity.insert_recursive::<Children>(Pickable::IGNORE);
+    }
+    entity.id()
+}
+
+fn progress_label(progress: f32) -> String {
+    format!("{:.0}%", progress.clamp(0., 1.) * 100.)
+}
+
+/// Accessibility node announcing `msg` to screen readers
+fn alert_node(msg: &str) -> AccessNode {
+    let mut node = AccessNode::new(Role::Alert);
+    node.set_label(msg);
+    node
+}
+
+/// Timer of the fully shown state. Paused forever for sticky notifications.
+fn show_timer(show_time: f32) -> Timer {
+    let mut timer = Timer::from_seconds(show_time.max(0.), TimerMode::Once);
+    if show_time <= 0. {
+        timer.pause();
+    }
+    timer
+}
+
+/// Timer of a fade. Negative or NaN durations skip it, too long ones never end.
+fn fade_timer(duration: f32) -> Timer {
+    let duration = Duration::try_from_secs_f32(duration.max(0.)).unwrap_or(Duration::MAX);
+    Timer::new(duration, TimerMode::Once)
+}
+
+/// Fade out the notification, or despawn it right away if it has no fade-out
+fn dismiss_noti_box(
+    commands: &mut Commands,
+    entity: Entity,
+    id: NotiBoxId,
+    noti_box: &mut NotiBox,
+    reason: DismissReason,
+) {
+    if !noti_box.dismiss(reason) {
+        despawn_noti_box(commands, entity, id, reason);
+    }
+}
+
+/// Despawn the notification, or put it back in the pool, and tell it is gone
+fn despawn_noti_box(commands: &mut Commands, entity: Entity, id: NotiBoxId, reason: DismissReason) {
+    commands.queue(move |world: &mut World| {
+        // Already dismissed by another system this frame, maybe even reused since
+        if world.get::<NotiBoxId>(entity) != Some(&id) {
+            return;
+        }
+        // Before the entity and its observers are gone
+        world.trigger(OnNotiDismissed { entity, id, reason });
+        world.write_message(NotiBoxDismissed { entity, id, reason });
+        pool::recycle(world.entity_mut(entity));
+    });
+}
+
+fn listen_dismiss(
+    mut commands: Commands,
+    mut event: MessageReader<DismissNoti>,
+    mut all_event: MessageReader<DismissAllNotiBoxes>,
+    mut queue: ResMut<NotiQueue>,
+    mut dropped: MessageWriter<NotiBoxDropped>,
+    mut query: Query<(Entity, &NotiBoxId, &mut NotiBox)>,
+) {
+    for DismissNoti(id) in event.read() {
+        queue.remove(&mut dropped, |noti| noti.id == Some(*id));
+        for (e, noti_id, mut noti_box) in query.iter_mut().filter(|(_, noti_id, _)| *noti_id == id) {
+            dismiss_noti_box(&mut commands, e, *noti_id, &mut noti_box, DismissReason::Api);
+        }
+    }
+
+    for filter in all_event.read() {
+        queue.remove(&mut dropped, |noti| filter.matches(noti.pos, &noti.tag));
+        for (e, noti_id, mut noti_box) in query.iter_mut() {
+            if filter.matches(noti_box.pos, &noti_box.tag) {
+                dismiss_noti_box(&mut commands, e, *noti_id, &mut noti_box, DismissReason::Api);
+            }
+        }
+    }
+}
+
+fn listen_update(
+    mut event: MessageReader<UpdateNotiBox>,
+    settings: Res<NotiBoxSettings>,
+    mut queue: ResMut<NotiQueue>,
+    mut query: Query<(Entity, &NotiBoxId, &mut NotiBox)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text, With<NotiBoxText>>,
+    mut accessible: Query<&mut AccessibilityNode, With<NotiBox>>,
+) {
+    for update in event.read() {
+        let id = update.id;
+        for noti in queue.0.iter_mut().filter(|noti| noti.id == Some(id)) {
+            if let Some(msg) = &update.msg {
+                noti.msg = msg.clone();
+            }
+            if let Some(show_time) = update.show_time {
+                noti.show_time = show_time;
+            }
+            if update.progress.is_some() && noti.progress.is_some() {
+                noti.progress = update.progress;
+            }
+        }
+
+        for (e, _, mut noti_box) in query.iter_mut().filter(|(_, noti_id, _)| **noti_id == id) {
+            if update.show_time.is_some() {
+                noti_box.restart(update.show_time);
+            }
+            if let (Some(progress), Some(fill)) = (update.progress, noti_box.fill) {
+                noti_box.fill = Some(progress);
+                if fill < 1. && progress >= 1. {
+                    noti_box.restart(Some(settings.progress_linger.max(f32::EPSILON)));
+                }
+            }
+            let Some(msg) = &update.msg else {
+                continue;
+            };
+            noti_box.msg = msg.clone();
+            // The new message is cut again by `truncate_text` if it does not fit
+            noti_box.truncated = false;
+            if let Ok(mut node) = accessible.get_mut(e) {
+                node.set_label(msg.as_str());
+            }
+            for child in children.iter_descendants(e) {
+                if let Ok(mut text) = texts.get_mut(child) {
+                    text.0 = msg.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Expand the notification if it is truncated, otherwise dismiss it unless it has another way to be dismissed
+fn click_noti_box(
+    commands: &mut Commands,
+    clicked: &mut MessageWriter<NotiBoxClicked>,
+    entity: Entity,
+    id: NotiBoxId,
+    noti_box: &mut NotiBox,
+    settings: &NotiBoxSettings,
+) {
+    clicked.write(NotiBoxClicked { entity, id });
+    if noti_box.truncated {
+        noti_box.max_lines = None;
+        noti_box.truncated = false;
+        noti_box.restart(None);
+        return;
+    }
+    if noti_box.close_button || settings.swipe_to_dismiss {
+        return;
+    }
+    despawn_noti_box(commands, entity, id, DismissReason::Clicked);
+}
+
+#[cfg(not(feature = "picking"))]
+fn listen_click(
+    mut commands: Commands,
+    mut clicked: MessageWriter<NotiBoxClicked>,
+    mut query: Query<(&Interaction, Entity, &NotiBoxId, &mut NotiBox), Changed<Interaction>>,
+    settings: Res<NotiBoxSettings>,
+    touches: Option<Res<Touches>>,
+) {
+    // Touches are handled on release by `input::listen_touch`
+    if touches.is_some_and(|touches| touches.any_just_pressed()) {
+        return;
+    }
+    for (i, e, id, mut noti_box) in query.iter_mut() {
+        if *i == Interaction::Pressed && noti_box.interactive {
+            click_noti_box(&mut commands, &mut clicked, e, *id, &mut noti_box, &settings);
+        }
+    }
+}
+
+fn listen_close_button(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &NotiCloseButton), Changed<Interaction>>,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox)>,
+) {
+    for (i, button) in buttons.iter() {
+        if *i != Interaction::Pressed {
+            continue;
+        }
+        if let Ok((id, mut noti_box)) = noti_boxes.get_mut(button.noti_box) {
+            dismiss_noti_box(
+                &mut commands,
+                button.noti_box,
+                *id,
+                &mut noti_box,
+                DismissReason::Clicked,
+            );
+        }
+    }
+}
+
+fn listen_copy_button(
+    buttons: Query<(&Interaction, &NotiCopyButton), Changed<Interaction>>,
+    noti_boxes: Query<(&NotiBoxId, &NotiBox)>,
+    mut copy: MessageWriter<NotiCopyRequested>,
+) {
+    for (i, button) in buttons.iter() {
+        if *i != Interaction::Pressed {
+            continue;
+        }
+        if let Ok((id, noti_box)) = noti_boxes.get(button.noti_box) {
+            copy.write(NotiCopyRequested {
+                id: *id,
+                text: noti_box.msg.clone(),
+            });
+        }
+    }
+}
+
+fn listen_action_button(
+    mut commands: Commands,
+    mut triggered: MessageWriter<NotiActionTriggered>,
+    buttons: Query<(&Interaction, &NotiActionButton), Changed<Interaction>>,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox)>,
+) {
+    for (i, button) in buttons.iter() {
+        if *i != Interaction::Pressed {
+            continue;
+        }
+        if let Ok((id, mut noti_box)) = noti_boxes.get_mut(button.noti_box) {
+            triggered.write(NotiActionTriggered {
+                id: *id,
+                action: button.action.clone(),
+            });
+            dismiss_noti_box(
+                &mut commands,
+                button.noti_box,
+                *id,
+                &mut noti_box,
+                DismissReason::Action,
+            );
+        }
+    }
+}
+
+fn countdown(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &NotiBoxId,
+        &mut NotiBox,
+        &Interaction,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &mut Node,
+        &mut UiTransform,
+    )>,
+    children: Query<&Children>,
+    mut fades: Query<NotiFadeQuery, Without<NotiBox>>,
+    mut shown: MessageWriter<NotiBoxShown>,
+    settings: Res<NotiBoxSettings>,
+    time: NotiClock,
+) {
+    for (e, id, mut noti_box, interaction, mut bg_color, mut border_color, mut node, mut transform) in query.iter_mut()
+    {
+        let over = noti_box.interactive && (noti_box.pointer_over || *interaction != Interaction::None);
+        let hovered = settings.pause_on_hover && over;
+        let dismiss_reason = noti_box.dismiss_reason.unwrap_or(DismissReason::Timeout);
+        let mut animation = noti_box.entrance;
+        let exit = noti_box.exit;
+        let mut progress = None;
+        for (state, ref mut timer) in noti_box.states.iter_mut() {
+            if timer.is_finished() {
+                continue;
+            }
+            if hovered && *state == AnimationState::Middle {
+                progress = Some(1.);
+                break;
+            }
+            timer.tick(time.delta());
+            match state {
+                AnimationState::Start => {
+                    progress = Some(timer.fraction());
+
+                    if timer.just_finished() {
+                        shown.write(NotiBoxShown { entity: e, id: *id });
+                    }
+                }
+                AnimationState::Middle => {
+                    progress = Some(1.);
+                }
+                AnimationState::End => {
+                    progress = Some(timer.fraction_remaining());
+                    animation = exit;
+
+                    if timer.just_finished() {
+                        despawn_noti_box(&mut commands, e, *id, dismiss_reason);
+                    }
+                }
+            }
+            break;
+        }
+
+        if let Some(progress) = progress {
+            // Already fully shown: only the hover highlight can change its colors, only a swipe can move it
+            let settled = progress == 1. && noti_box.progress == 1. && over == noti_box.highlighted;
+            noti_box.progress = progress;
+            let alpha = if let Some(animator) = noti_box.animator.clone() {
+                if settled {
+                    continue;
+                }
+                let mut target = NotiAnimationTarget {
+                    node: &mut node,
+                    transform: &mut transform,
+                    alpha: 1.,
+                };
+                animator.animate(noti_box.state(), progress, &mut target);
+                target.alpha
+            } else {
+                let eased = noti_box.easing.sample_clamped(progress);
+                let mut translation = animation.translation(noti_box.pos, eased);
+                if let Val::Percent(x) = translation.x {
+                    translation.x = Val::Percent(x + noti_box.drag);
+                }
+                let scale = animation.scale(progress);
+                if transform.translation != translation || transform.scale != scale {
+                    transform.translation = translation;
+                    transform.scale = scale;
+                }
+                animation.alpha(eased)
+            };
+            if settled {
+                continue;
+            }
+            noti_box.set_alpha(
+                alpha,
+                over,
+                &mut bg_color,
+                &mut border_color,
+                children.iter_descendants(e),
+                &mut fades,
+            );
+            noti_box.highlighted = over;
+        }
+    }
+}
+
+fn update_progress_bar(
+    mut bars: Query<(&NotiProgressBar, &mut Node), Without<NotiProgressFill>>,
+    mut fills: Query<(&NotiProgressFill, &mut Node), Without<NotiProgressBar>>,
+    mut labels: Query<(&NotiProgressLabel, &mut Text)>,
+    noti_boxes: Query<&NotiBox>,
+) {
+    for (bar, mut node) in bars.iter_mut() {
+        if let Ok(noti_box) = noti_boxes.get(bar.noti_box) {
+            let width = Val::Percent(noti_box.remaining_fraction() * 100.);
+            if node.width != width {
+                node.width = width;
+            }
+        }
+    }
+    for (fill, mut node) in fills.iter_mut() {
+        if let Some(progress) = noti_boxes.get(fill.noti_box).ok().and_then(|noti_box| noti_box.fill) {
+            let width = Val::Percent(progress.clamp(0., 1.) * 100.);
+            if node.width != width {
+                node.width = width;
+            }
+        }
+    }
+    for (label, mut text) in labels.iter_mut() {
+        if let Some(progress) = noti_boxes.get(label.noti_box).ok().and_then(|noti_box| noti_box.fill) {
+            let progress = progress_label(progress);
+            if text.0 != progress {
+                text.0 = progress;
+            }
+        }
+    }
+}
+
+fn update_modal_overlay(
+    mut commands: Commands,
+    mut overlays: Query<(Entity, &NotiModalOverlay, &mut BackgroundColor)>,
+    noti_boxes: Query<&NotiBox>,
+) {
+    for (e, overlay, mut color) in overlays.iter_mut() {
+        let Ok(noti_box) = noti_boxes.get(overlay.noti_box) else {
+            commands.entity(e).despawn();
+            continue;
+        };
+        let faded = overlay.color.with_alpha(overlay.color.alpha() * noti_box.progress);
+        color.set_if_neq(BackgroundColor(faded));
+    }
+}
+
+fn update_spinner(
+    mut spinners: Query<(&NotiSpinner, &mut UiTransform, &mut BorderColor)>,
+    noti_boxes: Query<&NotiBox>,
+    time: NotiClock,
+) {
+    let angle = time.elapsed().as_secs_f32() * SPINNER_SPEED * std::f32::consts::TAU;
+    for (spinner, mut transform, mut border_color) in spinners.iter_mut() {
+        let Ok(noti_box) = noti_boxes.get(spinner.noti_box) else {
+            continue;
+        };
+        transform.rotation = Rot2::radians(angle);
+        let alpha = spinner.color.alpha() * noti_box.progress;
+        // Only the top quarter of the ring is drawn in full
+        let ring = BorderColor {
+            top: spinner.color.with_alpha(alpha),
+            ..BorderColor::all(spinner.color.with_alpha(alpha * 0.2))
+        };
+        border_color.set_if_neq(ring);
+    }
+}
+
+fn update_repeat_counter(
+    mut counters: Query<(&NotiRepeatCounter, &mut Text)>,
+    noti_boxes: Query<&NotiBox, Changed<NotiBox>>,
+) {
+    for (counter, mut text) in counters.iter_mut() {
+        if let Ok(noti_box) = noti_boxes.get(counter.noti_box) {
+            if noti_box.repeat > 1 {
+                let repeat = format!("×{}", noti_box.repeat);
+                if text.0 != repeat {
+                    text.0 = repeat;
+                }
+            }
+        }
+    }
+}
+
+/// How fast notifications slide to their new place in a stack, settled in about 200ms
+const REFLOW_RATE: f32 = 15.;
+
+/// Whether the plugin's systems ran since the last [`reflow`]
+#[derive(Resource, Default)]
+struct NotiSystemsRan(bool);
+
+/// Whether the plugin's systems ran in the last update of its schedule, for the observers reacting between them
+#[derive(Resource, Default)]
+struct NotiSystemsActive(bool);
+
+fn mark_ran(mut ran: ResMut<NotiSystemsRan>, mut active: ResMut<NotiSystemsActive>) {
+    ran.0 = true;
+    active.0 = true;
+}
+
+/// Slide notifications to their new place when the notifications before them in the stack come and go,
+/// instead of jumping there
+fn reflow(
+    mut ran: ResMut<NotiSystemsRan>,
+    settings: Res<NotiBoxSettings>,
+    time: NotiClock,
+    mut noti_boxes: Query<(Entity, &mut NotiBox, &mut Node, &UiTransform, &ComputedNode), Without<world::NotiWorldBox>>,
+    children: Query<&Children>,
+    mut transforms: Query<&mut UiGlobalTransform>,
+) {
+    ran.0 = false;
+    let decay = (-REFLOW_RATE * time.delta().as_secs_f32()).exp();
+    for (e, mut noti_box, mut node, transform, computed) in noti_boxes.iter_mut() {
+        let Ok(global) = transforms.get(e) else {
+            continue;
+        };
+        if computed.size() == Vec2::ZERO {
+            continue;
+        }
+        let scale = computed.inverse_scale_factor();
+        let translation = transform
+            .translation
+            .resolve(scale.recip(), computed.size(), Vec2::ZERO);
+        let slot = (global.translation - translation) * scale - noti_box.reflow;
+        let moved = noti_box.slot.replace(slot).map_or(Vec2::ZERO, |prev| prev - slot);
+
+        let mut reflow = noti_box.reflow * decay + moved;
+        if settings.reduced_motion || reflow.length() < 0.5 {
+            reflow = Vec2::ZERO;
+        }
+        // The layout of this frame already happened, move the notification and its content by hand
+        let shift = (reflow - noti_box.reflow) / scale;
+        if shift != Vec2::ZERO {
+            for entity in std::iter::once(e).chain(children.iter_descendants(e)) {
+                if let Ok(mut global) = transforms.get_mut(entity) {
+                    *global = (Affine2::from_translation(shift) * Affine2::from(*global)).into();
+                }
+            }
+        }
+        noti_box.reflow = reflow;
+        let (left, top) = (Val::Px(reflow.x), Val::Px(reflow.y));
+        if node.left != left || node.top != top {
+            node.left = left;
+            node.top = top;
+        }
+    }
+}
+
+fn type_text(clock: NotiClock, mut noti_boxes: Query<&mut NotiBox>) {
+    let delta = clock.delta().as_secs_f32();
+    for mut noti_box in noti_boxes.iter_mut() {
+        if noti_box.is_typing() {
+            noti_box.typed += noti_box.typewriter * delta;
+        }
+    }
+}
+
+/// Scroll the text of marquee notifications not fitting in their box, coming back from the right once it is gone
+fn scroll_marquee(
+    clock: NotiClock,
+    mut texts: Query<(&mut NotiMarquee, &mut Node, &ComputedNode, &ChildOf)>,
+    clips: Query<&ComputedNode, Without<NotiMarquee>>,
+) {
+    let delta = clock.delta().as_secs_f32();
+    for (mut marquee, mut node, text, child_of) in texts.iter_mut() {
+        let Ok(clip) = clips.get(child_of.parent()) else {
+            continue;
+        };
+        let text_width = text.size().x * text.inverse_scale_factor();
+        let clip_width = clip.size().x * clip.inverse_scale_factor();
+        if text_width <= clip_width {
+            marquee.offset = 0.;
+        } else {
+            marquee.offset -= marquee.speed * delta;
+            if marquee.offset < -text_width {
+                marquee.offset = clip_width;
+            }
+        }
+        if node.left != Val::Px(marquee.offset) {
+            node.left = Val::Px(marquee.offset);
+        }
+    }
+}
+
+/// Cut the message of notifications taking more than their `max_lines`, and bring it back once they are expanded.
+/// Show the part of the message typed so far.
+fn truncate_text(mut noti_boxes: Query<&mut NotiBox>, mut texts: Query<(&NotiBoxText, &mut Text, &TextLayoutInfo)>) {
+    for (noti_text, mut text, layout) in texts.iter_mut() {
+        let Ok(mut noti_box) = noti_boxes.get_mut(noti_text.noti_box) else {
+            continue;
+        };
+        let Some(max_lines) = noti_box.max_lines else {
+            if text.0 != noti_box.typed_msg() {
+                text.0 = noti_box.typed_msg().to_string();
+            }
+            continue;
+        };
+        // The layout is not up to date with a new message yet
+        if text.is_changed() {
+            continue;
+        }
+        if noti_box.is_typing() && text.0 != noti_box.typed_msg() {
+            text.0 = noti_box.typed_msg().to_string();
+            continue;
+        }
+
+        let mut line_tops: Vec<f32> = layout.section_rects.iter().map(|(_, rect)| rect.min.y).collect();
+        line_tops.sort_by(f32::total_cmp);
+        line_tops.dedup();
+        let Some(&hidden_top) = line_tops.get(max_lines) else {
+            continue;
+        };
+        // Spans are left as is, only the message is cut
+        let Some(glyph) = layout
+            .glyphs
+            .iter()
+            .find(|glyph| glyph.span_index == 0 && glyph.position.y > hidden_top)
+        else {
+            continue;
+        };
+
+        // Glyph byte indices start over at every line break
+        let line_start: usize = text
+            .0
+            .split('\n')
+            .take(glyph.line_index)
+            .map(|line| line.len() + 1)
+            .sum();
+        let end = (line_start + glyph.byte_index).min(text.0.len());
+        let end = text.0[..end].char_indices().last().map_or(0, |(i, _)| i);
+        text.0 = format!("{}…", text.0[..end].trim_end());
+        noti_box.truncated = true;
+        // Typing stops where the message is cut
+        noti_box.typed = noti_box.msg.chars().count() as f32;
+    }
+}
+
+fn update_containers(settings: Res<NotiBoxSettings>, mut containers: Query<(&NotiContainer, &mut Node)>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (container, mut node) in containers.iter_mut() {
+        node.set_if_neq(pos_to_style(&container.pos, &settings));
+    }
+}
+
+fn box_style(noti: &NotiBoxEvent, theme: &NotiBoxTheme) -> Node {
+    Node {
+        width: noti.width,
+        height: noti.height,
+        max_width: noti.max_width.unwrap_or(theme.max_width),
+        min_width: noti.min_width.unwrap_or(theme.min_width),
+        min_height: noti.min_height.unwrap_or(theme.min_height),
+        max_height: noti.max_height.unwrap_or(theme.max_height),
+        margin: theme.scaled_rect(theme.margin),
+        border: noti.border.unwrap_or(theme.border),
+        padding: theme.scaled_rect(theme.padding),
+        flex_direction: FlexDirection::Column,
+        justify_content: noti.justify_content.unwrap_or(theme.justify_content),
+        align_content: AlignContent::Center,
+        align_items: noti.align_items.unwrap_or(theme.align_items),
+        justify_items: JustifyItems::Center,
+        ..default()
+    }
+}
+
+/// Full-screen column container whose content is anchored at `pos`, inside the safe area
+fn pos_to_style(pos: &NotiPosition, settings: &NotiBoxSettings) -> Node {
+    // The screen minus its safe area, with the offset of the notifications inside it
+    let mut ret = Node {
+        position_type: PositionType::Absolute,
+        left: settings.safe_area.left,
+        right: settings.safe_area.right,
+        top: settings.safe_area.top,
+        bottom: settings.safe_area.bottom,
+        padding: settings.offsets.get(pos).copied().unwrap_or_default(),
+        flex_direction: FlexDirection::Column,
+        ..default()
+    };
+
+    // Main axis is vertical, cross axis is horizontal
+    match pos {
+        NotiPosition::TopLeft => {
+            ret.align_items = AlignItems::FlexStart;
+            ret.justify_content = JustifyContent::FlexStart;
+        }
+        NotiPosition::TopMid => {
+            ret.align_items = AlignItems::Center;
+            ret.justify_content = JustifyContent::FlexStart;
+        }
+        NotiPosition::TopRight => {
+            ret.align_items = AlignItems::FlexEnd;
+            ret.justify_content = JustifyContent::FlexStart;
+        }
+        NotiPosition::MidLeft => {
+            ret.align_items = AlignItems::FlexStart;
+            ret.justify_content = JustifyContent::Center;
+        }
+        NotiPosition::Center => {
+            ret.align_items = AlignItems::Center;
+            ret.justify_content = JustifyContent::Center;
         }
         NotiPosition::MidRight => {
-            ret.justify_self = JustifySelf::End;
-            ret.align_self = AlignSelf::Center;
+            ret.align_items = AlignItems::FlexEnd;
+            ret.justify_content = JustifyContent::Center;
         }
         NotiPosition::BotLeft => {
-            ret.justify_self = JustifySelf::Start;
-            ret.align_self = AlignSelf::FlexEnd;
+            ret.align_items = AlignItems::FlexStart;
+            ret.justify_content = JustifyContent::FlexEnd;
         }
         NotiPosition::BotMid => {
-            ret.justify_self = JustifySelf::Center;
-            ret.align_self = AlignSelf::FlexEnd;
+            ret.align_items = AlignItems::Center;
+            ret.justify_content = JustifyContent::FlexEnd;
         }
         NotiPosition::BotRight => {
-            ret.justify_self = JustifySelf::End;
-            ret.align_self = AlignSelf::FlexEnd;
+            ret.align_items = AlignItems::FlexEnd;
+            ret.justify_content = JustifyContent::FlexEnd;
+        }
+        NotiPosition::Custom { left, top } => {
+            ret.left = *left;
+            ret.top = *top;
+            ret.right = Val::Auto;
+            ret.bottom = Val::Auto;
+            ret.align_items = AlignItems::FlexStart;
         }
     }
 
+    if settings.stack_orders.get(pos).copied().unwrap_or_default() == NotiStackOrder::NewestAbove {
+        // Reversing the main axis also swaps its start and end
+        ret.flex_direction = FlexDirection::ColumnReverse;
+        ret.justify_content = match ret.justify_content {
+            JustifyContent::FlexStart => JustifyContent::FlexEnd,
+            JustifyContent::FlexEnd => JustifyContent::FlexStart,
+            justify => justify,
+        };
+    }
+
     ret
 }
@@ -0,0 +1,38 @@
+use bevy::platform::collections::HashMap;
+use std::{collections::VecDeque, time::Duration};
+
+/// Throttle incoming notifications, see [`NotiBoxSettings::max_per_second`](crate::NotiBoxSettings::max_per_second)
+/// and [`NotiBoxSettings::repeat_interval`](crate::NotiBoxSettings::repeat_interval)
+#[derive(Default)]
+pub(crate) struct RateLimiter {
+    /// When the notifications of the last second were accepted
+    recent: VecDeque<Duration>,
+    /// When each message was last accepted
+    last_seen: HashMap<String, Duration>,
+}
+
+impl RateLimiter {
+    /// Return `true` if a notification with `msg` arriving at `now` may be shown
+    pub(crate) fn allow(&mut self, msg: &str, now: Duration, max_per_second: u32, repeat_interval: f32) -> bool {
+        let second = Duration::from_secs(1);
+        while self.recent.front().is_some_and(|t| now.saturating_sub(*t) >= second) {
+            self.recent.pop_front();
+        }
+        if max_per_second > 0 && self.recent.len() >= max_per_second as usize {
+            return false;
+        }
+
+        if repeat_interval > 0. {
+            // Too long to represent, the message is never repeated
+            let interval = Duration::try_from_secs_f32(repeat_interval).unwrap_or(Duration::MAX);
+            self.last_seen.retain(|_, t| now.saturating_sub(*t) < interval);
+            if self.last_seen.contains_key(msg) {
+                return false;
+            }
+            self.last_seen.insert(msg.to_string(), now);
+        }
+
+        self.recent.push_back(now);
+        true
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{limit::RateLimiter, NotiBoxEvent, NotiClock, NotiLevel};
+use bevy::{
+    log::{
+        tracing::{
+            field::{Field, Visit},
+            Event, Level, Subscriber,
+        },
+        tracing_subscriber::{layer::Context, Layer},
+        BoxedLayer,
+    },
+    prelude::*,
+};
+use std::{
+    fmt::Debug,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex, PoisonError,
+    },
+};
+
+/// Channel the notifications from [`noti_log_layer`] are sent to
+pub const LOG_CHANNEL: &str = "log";
+
+/// Show `warn!` and `error!` logs from anywhere in the app as notifications, in the [`LOG_CHANNEL`] channel.
+/// Set it as the custom layer of the `LogPlugin`:
+///
+/// ```no_run
+/// # use bevy::{log::LogPlugin, prelude::*};
+/// # use bevy_noti_box::*;
+/// App::new()
+///     .add_plugins(DefaultPlugins.set(LogPlugin {
+///         custom_layer: noti_log_layer,
+///         ..default()
+///     }))
+///     .add_plugins(NotiBoxPluginAnyState::any());
+/// ```
+pub fn noti_log_layer(app: &mut App) -> Option<BoxedLayer> {
+    let (sender, receiver) = channel();
+    app.insert_resource(NotiLogReceiver(Mutex::new(receiver)))
+        .init_resource::<NotiLogSettings>();
+    Some(Box::new(NotiLogLayer { sender }))
+}
+
+/// Which logs [`noti_log_layer`] shows
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, opaque)]
+pub struct NotiLogSettings {
+    /// Least severe level shown, `WARN` or `ERROR`
+    pub min_level: Level,
+    /// Drop logs beyond this many per second. `0` means no limit.
+    pub max_per_second: u32,
+    /// Drop logs whose message was already shown less than this many seconds ago. `0.` means no limit.
+    pub repeat_interval: f32,
+}
+
+impl Default for NotiLogSettings {
+    fn default() -> Self {
+        Self {
+            min_level: Level::WARN,
+            max_per_second: 3,
+            repeat_interval: 5.,
+        }
+    }
+}
+
+struct NotiLogLayer {
+    sender: Sender<(Level, String)>,
+}
+
+/// Collects the message of a log
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0 = value.to_string();
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for NotiLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        // Logs of this crate could loop back into notifications
+        if *metadata.level() > Level::WARN || metadata.target().starts_with(env!("CARGO_CRATE_NAME")) {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let _ = self.sender.send((*metadata.level(), visitor.0));
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct NotiLogReceiver(Mutex<Receiver<(Level, String)>>);
+
+pub(crate) fn listen_log(
+    receiver: Option<Res<NotiLogReceiver>>,
+    settings: Option<Res<NotiLogSettings>>,
+    time: NotiClock,
+    mut limiter: Local<RateLimiter>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+) {
+    let (Some(receiver), Some(settings)) = (receiver, settings) else {
+        return;
+    };
+    let receiver = receiver.0.lock().unwrap_or_else(PoisonError::into_inner);
+    for (level, msg) in receiver.try_iter() {
+        if level > settings.min_level
+            || !limiter.allow(&msg, time.elapsed(), settings.max_per_second, settings.repeat_interval)
+        {
+            continue;
+        }
+        let level = if level == Level::ERROR {
+            NotiLevel::Error
+        } else {
+            NotiLevel::Warning
+        };
+        notis.write(NotiBoxEvent {
+            channel: Some(LOG_CHANNEL.to_string()),
+            ..NotiBoxEvent::from_level(level, msg)
+        });
+    }
+}
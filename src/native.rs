@@ -0,0 +1,114 @@
+use crate::{NotiBoxEvent, NotiBoxId, NotiLevel};
+use bevy::prelude::*;
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+use bevy::tasks::IoTaskPool;
+#[cfg(any(
+    all(feature = "native", not(target_arch = "wasm32")),
+    all(feature = "web", target_arch = "wasm32")
+))]
+use bevy::window::PrimaryWindow;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+use web_sys::{Notification, NotificationOptions, NotificationPermission};
+
+/// Sent instead of showing a notification while the game window is unfocused or hidden, when
+/// [`NotiBoxSettings::native_when_unfocused`](crate::NotiBoxSettings::native_when_unfocused) is `true`.
+/// With the `native` feature, they are shown by the OS with `notify-rust`, titled with the window title.
+/// Otherwise forward it to the notification center of the OS yourself, e.g.:
+///
+/// ```ignore
+/// fn forward(mut event: MessageReader<NotiNativeRequest>) {
+///     for noti in event.read() {
+///         let _ = notify_rust::Notification::new().summary("My Game").body(&noti.msg).show();
+///     }
+/// }
+/// ```
+///
+/// On the web, the `web` feature shows them with the Notifications API of the browser once the player allowed it.
+/// Browsers may only ask for the permission after a click, so call [`request_web_permission`] early, e.g. when a
+/// settings checkbox is clicked. Otherwise it is asked for with the first notification.
+#[derive(Message, Clone, Debug)]
+pub struct NotiNativeRequest {
+    pub id: Option<NotiBoxId>,
+    pub msg: String,
+    pub level: NotiLevel,
+    pub tag: Option<String>,
+}
+
+impl From<&NotiBoxEvent> for NotiNativeRequest {
+    fn from(noti: &NotiBoxEvent) -> Self {
+        Self {
+            id: noti.id,
+            msg: noti.msg.clone(),
+            level: noti.level,
+            tag: noti.tag.clone(),
+        }
+    }
+}
+
+/// Show the notifications handed to the OS, titled with the window title
+#[cfg(all(feature = "native", not(target_arch = "wasm32")))]
+pub(crate) fn show_native(mut event: MessageReader<NotiNativeRequest>, windows: Query<&Window, With<PrimaryWindow>>) {
+    let title = windows
+        .iter()
+        .next()
+        .map(|window| window.title.as_str())
+        .unwrap_or_default();
+    for noti in event.read() {
+        let mut notification = notify_rust::Notification::new();
+        notification.summary(title).body(&noti.msg);
+        // Talking to the notification daemon may take a while
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = notification.show() {
+                    warn!("Failed to show a notification with the OS: {err}");
+                }
+            })
+            .detach();
+    }
+}
+
+/// Ask the player to allow the browser notifications sent for [`NotiNativeRequest`]
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub fn request_web_permission() {
+    if let Err(err) = Notification::request_permission() {
+        warn!("Failed to ask for the notification permission: {err:?}");
+    }
+}
+
+/// Show the notifications handed to the browser, titled with the window title.
+/// The ones sent while the player is asked for the permission are shown once allowed.
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub(crate) fn show_web(
+    mut event: MessageReader<NotiNativeRequest>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut pending: Local<Vec<String>>,
+    mut asked: Local<bool>,
+) {
+    pending.extend(event.read().map(|noti| noti.msg.clone()));
+    if pending.is_empty() {
+        return;
+    }
+    match Notification::permission() {
+        NotificationPermission::Granted => {
+            let title = windows
+                .iter()
+                .next()
+                .map(|window| window.title.as_str())
+                .unwrap_or_default();
+            for msg in pending.drain(..) {
+                let options = NotificationOptions::new();
+                options.set_body(&msg);
+                if let Err(err) = Notification::new_with_options(title, &options) {
+                    warn!("Failed to show a notification in the browser: {err:?}");
+                }
+            }
+        }
+        NotificationPermission::Default => {
+            if !*asked {
+                *asked = true;
+                request_web_permission();
+            }
+        }
+        _ => pending.clear(),
+    }
+}
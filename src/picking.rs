@@ -0,0 +1,118 @@
+use crate::{click_noti_box, NotiBox, NotiBoxClicked, NotiBoxId, NotiBoxSettings, NotiSystemsActive};
+use bevy::{
+    picking::pointer::{PointerButton, PointerId},
+    prelude::*,
+};
+
+/// How far, in percent of its width, a notification can be dragged and still be clicked
+const CLICK_SLOP: f32 = 2.;
+
+/// What a pointer did on a notification
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotiPointerKind {
+    Click(PointerButton),
+    Over,
+    Out,
+}
+
+/// Sent when a pointer clicks, enters or leaves an interactive notification, as seen by `bevy_picking`.
+/// Unlike [`NotiBoxClicked`], it also comes from custom picking backends.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct NotiPointerEvent {
+    pub entity: Entity,
+    pub id: NotiBoxId,
+    pub pointer_id: PointerId,
+    pub kind: NotiPointerKind,
+}
+
+/// Interactive notification reached by a pointer event while it bubbles up,
+/// `None` while the plugin's systems do not run, e.g. outside of its states
+fn interactive<'a>(
+    entity: Entity,
+    noti_boxes: &'a mut Query<(&NotiBoxId, &mut NotiBox)>,
+    active: &NotiSystemsActive,
+) -> Option<(NotiBoxId, Mut<'a, NotiBox>)> {
+    if !active.0 {
+        return None;
+    }
+    noti_boxes
+        .get_mut(entity)
+        .ok()
+        .filter(|(_, noti_box)| noti_box.interactive)
+        .map(|(id, noti_box)| (*id, noti_box))
+}
+
+/// Click the notification with the primary button, like [`Interaction::Pressed`] does without the `picking` feature.
+/// Touches are left to `input::listen_touch`, which also handles long presses.
+pub(crate) fn on_click(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox)>,
+    buttons: Query<(), With<Button>>,
+    parents: Query<&ChildOf>,
+    settings: Res<NotiBoxSettings>,
+    active: Res<NotiSystemsActive>,
+    mut clicked: MessageWriter<NotiBoxClicked>,
+    mut events: MessageWriter<NotiPointerEvent>,
+) {
+    let entity = click.entity;
+    let Some((id, mut noti_box)) = interactive(entity, &mut noti_boxes, &active) else {
+        return;
+    };
+    events.write(NotiPointerEvent {
+        entity,
+        id,
+        pointer_id: click.pointer_id,
+        kind: NotiPointerKind::Click(click.event.button),
+    });
+
+    // Its buttons handle their own clicks
+    let original = click.original_event_target();
+    let on_button = std::iter::once(original)
+        .chain(parents.iter_ancestors(original))
+        .take_while(|e| *e != entity)
+        .any(|e| buttons.contains(e));
+    if click.event.button != PointerButton::Primary
+        || click.pointer_id.is_touch()
+        || on_button
+        || noti_box.is_leaving()
+        || noti_box.drag.abs() > CLICK_SLOP
+    {
+        return;
+    }
+    click_noti_box(&mut commands, &mut clicked, entity, id, &mut noti_box, &settings);
+}
+
+pub(crate) fn on_over(
+    over: On<Pointer<Over>>,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox)>,
+    active: Res<NotiSystemsActive>,
+    mut events: MessageWriter<NotiPointerEvent>,
+) {
+    if let Some((id, mut noti_box)) = interactive(over.entity, &mut noti_boxes, &active) {
+        noti_box.pointer_over = true;
+        events.write(NotiPointerEvent {
+            entity: over.entity,
+            id,
+            pointer_id: over.pointer_id,
+            kind: NotiPointerKind::Over,
+        });
+    }
+}
+
+pub(crate) fn on_out(
+    out: On<Pointer<Out>>,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox)>,
+    active: Res<NotiSystemsActive>,
+    mut events: MessageWriter<NotiPointerEvent>,
+) {
+    if let Some((id, mut noti_box)) = interactive(out.entity, &mut noti_boxes, &active) {
+        noti_box.pointer_over = false;
+        events.write(NotiPointerEvent {
+            entity: out.entity,
+            id,
+            pointer_id: out.pointer_id,
+            kind: NotiPointerKind::Out,
+        });
+    }
+}
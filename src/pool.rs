@@ -0,0 +1,109 @@
+use crate::{world::NotiWorldBox, NotiBox, NotiBoxId, NotiBoxSettings, NotiModalOverlay};
+use bevy::{
+    a11y::AccessibilityNode,
+    ecs::{observer::ObservedBy, system::SystemParam},
+    prelude::*,
+};
+
+/// Marks a hidden notification entity waiting to be reused
+#[derive(Component)]
+pub(crate) struct NotiPooled;
+
+/// Entities [`recycle`] put aside, see [`NotiBoxSettings::pool_size`]
+#[derive(Resource, Default)]
+pub(crate) struct NotiPool(Vec<Entity>);
+
+#[derive(SystemParam)]
+pub(crate) struct NotiPoolParam<'w, 's> {
+    pool: ResMut<'w, NotiPool>,
+    pooled: Query<'w, 's, (), With<NotiPooled>>,
+}
+
+impl NotiPoolParam<'_, '_> {
+    /// Entity to spawn a notification on, skipping the ones despawned by someone else
+    pub(crate) fn take(&mut self) -> Option<Entity> {
+        while let Some(e) = self.pool.0.pop() {
+            if self.pooled.contains(e) {
+                return Some(e);
+            }
+        }
+        None
+    }
+}
+
+fn hidden() -> (NotiPooled, Node) {
+    (
+        NotiPooled,
+        Node {
+            display: Display::None,
+            ..default()
+        },
+    )
+}
+
+/// Spawn or despawn entities to keep [`NotiBoxSettings::pool_size`] of them ready, counting the shown notifications
+/// that go back to the pool. Refills the pool when notifications are despawned by someone else, e.g. with their parent.
+pub(crate) fn fill_pool(
+    mut commands: Commands,
+    settings: Res<NotiBoxSettings>,
+    mut pool: ResMut<NotiPool>,
+    pooled: Query<(), With<NotiPooled>>,
+    shown: Query<&NotiBox, Without<NotiWorldBox>>,
+) {
+    if !pool.0.iter().all(|e| pooled.contains(*e)) {
+        pool.0.retain(|e| pooled.contains(*e));
+    }
+    let in_use = shown.iter().filter(|noti_box| noti_box.recyclable).count();
+    let size = settings.pool_size.saturating_sub(in_use);
+    if pool.0.len() < size {
+        // Put in the pool once spawned, `take` would throw it away before
+        let missing = size - pool.0.len();
+        commands.queue(move |world: &mut World| {
+            for _ in 0..missing {
+                let e = world.spawn(hidden()).id();
+                world.resource_mut::<NotiPool>().0.push(e);
+            }
+        });
+    }
+    if pool.0.len() > size {
+        for e in pool.0.drain(size..) {
+            commands.entity(e).try_despawn();
+        }
+    }
+}
+
+/// Clear a notification and hide it to be reused, or despawn it if the pool is full
+pub(crate) fn recycle(mut entity: EntityWorldMut) {
+    let id = entity.id();
+    let recyclable =
+        entity.get::<NotiBox>().is_some_and(|noti_box| noti_box.recyclable) && !entity.contains::<NotiWorldBox>();
+    let full = entity.world_scope(|world| {
+        let pool_size = world.resource::<NotiBoxSettings>().pool_size;
+        world.resource::<NotiPool>().0.len() >= pool_size
+    });
+    if !recyclable || full {
+        entity.despawn();
+        return;
+    }
+
+    entity.despawn_related::<Children>();
+    entity.remove::<(NotiBox, NotiBoxId, ChildOf, AccessibilityNode, ObservedBy)>();
+    #[cfg(feature = "audio")]
+    entity.remove::<crate::audio::NotiDismissSound>();
+    #[cfg(feature = "picking")]
+    entity.remove::<Pickable>();
+    entity.insert((hidden(), Interaction::None));
+    entity.world_scope(|world| {
+        // The overlay would otherwise stay for the next notification
+        let overlays: Vec<_> = world
+            .query::<(Entity, &NotiModalOverlay)>()
+            .iter(world)
+            .filter(|(_, overlay)| overlay.noti_box == id)
+            .map(|(e, _)| e)
+            .collect();
+        for e in overlays {
+            world.despawn(e);
+        }
+        world.resource_mut::<NotiPool>().0.push(id);
+    });
+}
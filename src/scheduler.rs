@@ -0,0 +1,70 @@
+use crate::{NotiBoxEvent, NotiClock};
+use bevy::prelude::*;
+
+/// Identifies a recurring notification so it can be stopped with [`NotiScheduler::remove`]
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Reflect)]
+pub struct NotiScheduleId(u64);
+
+struct Recurring {
+    id: NotiScheduleId,
+    noti: NotiBoxEvent,
+    timer: Timer,
+    /// Times left to send it, `None` for forever
+    repeats_left: Option<u32>,
+}
+
+/// Notifications sent again and again, e.g. an "Autosave complete" toast or a reminder to take a break
+#[derive(Resource, Default)]
+pub struct NotiScheduler {
+    next_id: u64,
+    recurring: Vec<Recurring>,
+}
+
+impl NotiScheduler {
+    /// Send `noti` every `interval` seconds, starting one interval from now.
+    /// It is sent `max_repeats` times, or until removed if `None`.
+    pub fn add(&mut self, noti: impl Into<NotiBoxEvent>, interval: f32, max_repeats: Option<u32>) -> NotiScheduleId {
+        let id = NotiScheduleId(self.next_id);
+        self.next_id += 1;
+        self.recurring.push(Recurring {
+            id,
+            noti: noti.into(),
+            timer: Timer::from_seconds(interval.max(0.), TimerMode::Repeating),
+            repeats_left: max_repeats,
+        });
+        id
+    }
+
+    /// Stop sending a notification. Returns `false` if it was already done.
+    pub fn remove(&mut self, id: NotiScheduleId) -> bool {
+        let len = self.recurring.len();
+        self.recurring.retain(|recurring| recurring.id != id);
+        self.recurring.len() != len
+    }
+
+    pub fn contains(&self, id: NotiScheduleId) -> bool {
+        self.recurring.iter().any(|recurring| recurring.id == id)
+    }
+
+    pub fn clear(&mut self) {
+        self.recurring.clear();
+    }
+}
+
+pub(crate) fn tick_scheduler(
+    time: NotiClock,
+    mut scheduler: ResMut<NotiScheduler>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+) {
+    scheduler.recurring.retain_mut(|recurring| {
+        recurring.timer.tick(time.delta());
+        // Several intervals may have gone by in a long frame, one notification is enough
+        if recurring.timer.just_finished() {
+            notis.write(recurring.noti.clone());
+            if let Some(repeats_left) = &mut recurring.repeats_left {
+                *repeats_left = repeats_left.saturating_sub(1);
+            }
+        }
+        recurring.repeats_left != Some(0)
+    });
+}
@@ -0,0 +1,55 @@
+use crate::{NotiBoxEvent, NotifyExt};
+use bevy::prelude::*;
+use std::sync::{
+    mpsc::{channel, Receiver, Sender},
+    Mutex, PoisonError,
+};
+
+/// Cloneable handle sending notifications from outside of systems, e.g. a background thread, an async task or
+/// a network callback. Get it from the resource.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_noti_box::*;
+/// fn start_download(sender: Res<NotiSender>) {
+///     let sender = sender.clone();
+///     std::thread::spawn(move || {
+///         // ...
+///         sender.send(NotiBoxEvent::success("Download complete"));
+///     });
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct NotiSender(Sender<NotiBoxEvent>);
+
+impl NotiSender {
+    /// Queue a notification, shown the next time the plugin's systems run.
+    /// Return `false` if the app is gone.
+    pub fn send(&self, noti: impl Into<NotiBoxEvent>) -> bool {
+        self.0.send(noti.into()).is_ok()
+    }
+}
+
+impl NotifyExt for NotiSender {
+    fn notify_with(&mut self, noti: NotiBoxEvent) {
+        self.send(noti);
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct NotiReceiver(Mutex<Receiver<NotiBoxEvent>>);
+
+/// Add the [`NotiSender`] resource, once for both plugins
+pub(crate) fn init(app: &mut App) {
+    if app.world().contains_resource::<NotiSender>() {
+        return;
+    }
+    let (sender, receiver) = channel();
+    app.insert_resource(NotiSender(sender))
+        .insert_resource(NotiReceiver(Mutex::new(receiver)));
+}
+
+pub(crate) fn listen_sender(receiver: Res<NotiReceiver>, mut notis: MessageWriter<NotiBoxEvent>) {
+    let receiver = receiver.0.lock().unwrap_or_else(PoisonError::into_inner);
+    notis.write_batch(receiver.try_iter());
+}
@@ -0,0 +1,66 @@
+use crate::{NotiBoxDismissed, NotiBoxDropped, NotiBoxEvent, NotiBoxId};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Show notifications one after another, each once the previous one went away, e.g. tutorial steps.
+/// A step that is never shown, e.g. dropped by rate limiting, is skipped.
+#[derive(Message, Clone)]
+pub struct NotiSequenceEvent(pub Vec<NotiBoxEvent>);
+
+impl NotiSequenceEvent {
+    pub fn new(steps: impl IntoIterator<Item = impl Into<NotiBoxEvent>>) -> Self {
+        Self(steps.into_iter().map(Into::into).collect())
+    }
+}
+
+struct Sequence {
+    /// Step being shown
+    current: NotiBoxId,
+    rest: VecDeque<NotiBoxEvent>,
+}
+
+/// Sequences still being shown
+#[derive(Resource, Default)]
+pub(crate) struct NotiSequences(Vec<Sequence>);
+
+/// Send the next step, returning its id
+fn send_next(rest: &mut VecDeque<NotiBoxEvent>, notis: &mut MessageWriter<NotiBoxEvent>) -> Option<NotiBoxId> {
+    let mut noti = rest.pop_front()?;
+    let id = *noti.id.get_or_insert_with(NotiBoxId::unique);
+    notis.write(noti);
+    Some(id)
+}
+
+pub(crate) fn listen_sequence(
+    mut event: MessageReader<NotiSequenceEvent>,
+    mut dismissed: MessageReader<NotiBoxDismissed>,
+    mut dropped: MessageReader<NotiBoxDropped>,
+    mut sequences: ResMut<NotiSequences>,
+    mut notis: MessageWriter<NotiBoxEvent>,
+) {
+    for sequence in event.read() {
+        let mut rest: VecDeque<NotiBoxEvent> = sequence.0.iter().cloned().collect();
+        if let Some(current) = send_next(&mut rest, &mut notis) {
+            sequences.0.push(Sequence { current, rest });
+        }
+    }
+
+    let gone = dismissed
+        .read()
+        .map(|dismissed| dismissed.id)
+        .chain(dropped.read().map(|dropped| dropped.id));
+    for id in gone {
+        sequences.0.retain_mut(|sequence| {
+            if sequence.current != id {
+                return true;
+            }
+            match send_next(&mut sequence.rest, &mut notis) {
+                Some(id) => {
+                    sequence.current = id;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized form of a [`TextFont`]. The font handle is left out, only the size is kept.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct FontDef {
+    font_size: f32,
+}
+
+impl Default for FontDef {
+    fn default() -> Self {
+        Self {
+            font_size: TextFont::default().font_size,
+        }
+    }
+}
+
+impl From<&TextFont> for FontDef {
+    fn from(font: &TextFont) -> Self {
+        Self {
+            font_size: font.font_size,
+        }
+    }
+}
+
+impl From<FontDef> for TextFont {
+    fn from(def: FontDef) -> Self {
+        TextFont::from_font_size(def.font_size)
+    }
+}
+
+pub(crate) mod font {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(font: &TextFont, serializer: S) -> Result<S::Ok, S::Error> {
+        FontDef::from(font).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TextFont, D::Error> {
+        FontDef::deserialize(deserializer).map(TextFont::from)
+    }
+}
+
+pub(crate) mod option_font {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(font: &Option<TextFont>, serializer: S) -> Result<S::Ok, S::Error> {
+        font.as_ref().map(FontDef::from).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<TextFont>, D::Error> {
+        Option::<FontDef>::deserialize(deserializer).map(|def| def.map(TextFont::from))
+    }
+}
+
+/// Serialized form of a [`LineHeight`](bevy::text::LineHeight)
+#[derive(Serialize, Deserialize)]
+enum LineHeightDef {
+    Px(f32),
+    RelativeToFont(f32),
+}
+
+pub(crate) mod option_line_height {
+    use super::*;
+    use bevy::text::LineHeight;
+
+    pub(crate) fn serialize<S: Serializer>(line_height: &Option<LineHeight>, serializer: S) -> Result<S::Ok, S::Error> {
+        line_height
+            .map(|line_height| match line_height {
+                LineHeight::Px(px) => LineHeightDef::Px(px),
+                LineHeight::RelativeToFont(scale) => LineHeightDef::RelativeToFont(scale),
+            })
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<LineHeight>, D::Error> {
+        Option::<LineHeightDef>::deserialize(deserializer).map(|def| {
+            def.map(|def| match def {
+                LineHeightDef::Px(px) => LineHeight::Px(px),
+                LineHeightDef::RelativeToFont(scale) => LineHeight::RelativeToFont(scale),
+            })
+        })
+    }
+}
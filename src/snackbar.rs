@@ -0,0 +1,50 @@
+use crate::{NotiAction, NotiActionButton, NotiBoxEvent, NotiBoxId, NotiPosition};
+use bevy::prelude::*;
+
+/// Seconds a snackbar stays, leaving time to undo
+const SNACKBAR_SHOW_TIME: f32 = 4.;
+
+/// Sent when the "Undo" button of a snackbar is clicked
+#[derive(Message, Clone, Debug)]
+pub struct UndoRequested {
+    pub id: NotiBoxId,
+    /// Given to [`NotiBoxEvent::snackbar`], e.g. the item to put back
+    pub payload: String,
+}
+
+/// Marks a notification created with [`NotiBoxEvent::snackbar`]
+#[derive(Component, Clone)]
+pub(crate) struct NotiSnackbar;
+
+impl NotiBoxEvent {
+    /// Short notification at the bottom with an "Undo" button, e.g. after deleting an item.
+    /// Clicking the button sends [`UndoRequested`] with `payload`.
+    pub fn snackbar(msg: impl Into<String>, payload: impl Into<String>) -> Self {
+        NotiBoxEvent {
+            msg: msg.into(),
+            pos: NotiPosition::BotMid,
+            show_time: SNACKBAR_SHOW_TIME,
+            actions: vec![NotiAction::new("Undo", payload)],
+            ..default()
+        }
+        .insert(NotiSnackbar)
+    }
+}
+
+pub(crate) fn listen_undo(
+    buttons: Query<(&Interaction, &NotiActionButton), Changed<Interaction>>,
+    snackbars: Query<&NotiBoxId, With<NotiSnackbar>>,
+    mut undo: MessageWriter<UndoRequested>,
+) {
+    for (i, button) in buttons.iter() {
+        if *i != Interaction::Pressed {
+            continue;
+        }
+        if let Ok(id) = snackbars.get(button.noti_box) {
+            undo.write(UndoRequested {
+                id: *id,
+                payload: button.action.payload.clone(),
+            });
+        }
+    }
+}
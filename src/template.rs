@@ -0,0 +1,107 @@
+use crate::NotiBoxEvent;
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+/// Notification described in a `.noti.ron` file, with the fields of [`NotiBoxEvent`].
+/// `{name}` placeholders in the message and spans are replaced by the arguments given to
+/// [`NotiBoxEvent::from_template`]. Loading templates needs [`AssetPlugin`] to be added before the notification plugin.
+///
+/// ```ron
+/// (msg: "Picked up {item}", pos: BotMid, level: Success, show_time: 2.)
+/// ```
+#[derive(Asset, TypePath, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NotiTemplate {
+    pub event: NotiBoxEvent,
+}
+
+impl NotiTemplate {
+    /// Notification with the placeholders replaced by `args`
+    pub fn instantiate(&self, args: &[(String, String)]) -> NotiBoxEvent {
+        let mut noti = self.event.clone();
+        noti.msg = fill(&noti.msg, args);
+        for span in noti.spans.iter_mut() {
+            span.text = fill(&span.text, args);
+        }
+        noti
+    }
+}
+
+fn fill(text: &str, args: &[(String, String)]) -> String {
+    let mut text = text.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), value);
+    }
+    text
+}
+
+/// Template a [`NotiBoxEvent`] is built from once the asset is loaded
+#[derive(Clone, Reflect)]
+pub struct NotiTemplateRef {
+    pub handle: Handle<NotiTemplate>,
+    pub args: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+pub(crate) struct NotiTemplateLoader;
+
+impl AssetLoader for NotiTemplateLoader {
+    type Asset = NotiTemplate;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<NotiTemplate, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["noti.ron"]
+    }
+}
+
+/// Turn notifications sent from a template into regular ones, holding them back until the template is loaded.
+/// Without an [`AssetServer`], e.g. in a headless app, notifications from a template are dropped.
+#[derive(SystemParam)]
+pub(crate) struct NotiTemplates<'w, 's> {
+    templates: Option<Res<'w, Assets<NotiTemplate>>>,
+    asset_server: Option<Res<'w, AssetServer>>,
+    pending: Local<'s, Vec<NotiBoxEvent>>,
+}
+
+impl NotiTemplates<'_, '_> {
+    pub(crate) fn resolve(&mut self, incoming: impl Iterator<Item = NotiBoxEvent>) -> Vec<NotiBoxEvent> {
+        let mut ready = Vec::new();
+        let pending: Vec<NotiBoxEvent> = self.pending.drain(..).chain(incoming).collect();
+        for noti in pending {
+            let Some(template) = &noti.template else {
+                ready.push(noti);
+                continue;
+            };
+            let (Some(templates), Some(asset_server)) = (&self.templates, &self.asset_server) else {
+                warn!("Dropped a notification from a template because there is no asset server");
+                continue;
+            };
+            if let Some(asset) = templates.get(&template.handle) {
+                let mut resolved = asset.instantiate(&template.args);
+                resolved.id = noti.id.or(resolved.id);
+                ready.push(resolved);
+            } else if asset_server.load_state(&template.handle).is_failed() {
+                warn!("Dropped a notification whose template failed to load");
+            } else {
+                self.pending.push(noti);
+            }
+        }
+        ready
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{DismissAllNotiBoxes, DismissNoti, NotiBoxEvent, UpdateNotiBox};
+use bevy::prelude::*;
+
+/// Record notifications in [`NotiRecorder`] to check them in tests.
+/// It works alone in a headless [`App`], without [`NotiBoxPlugin`](crate::NotiBoxPlugin) nor any UI plugin,
+/// or along with it to also show them.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_noti_box::*;
+/// fn save(mut commands: Commands) {
+///     commands.notify("Saved!");
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, NotiBoxTestPlugin))
+///     .add_systems(Update, save);
+/// app.update();
+/// app.assert_notified("Saved!");
+/// ```
+pub struct NotiBoxTestPlugin;
+
+impl Plugin for NotiBoxTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<NotiBoxEvent>()
+            .add_message::<DismissNoti>()
+            .add_message::<DismissAllNotiBoxes>()
+            .add_message::<UpdateNotiBox>()
+            .init_resource::<NotiRecorder>()
+            .add_systems(Last, (crate::sender::listen_sender, record).chain());
+        crate::sender::init(app);
+    }
+}
+
+/// Every notification sent since the app started, oldest first
+#[derive(Resource, Default)]
+pub struct NotiRecorder(Vec<NotiBoxEvent>);
+
+impl NotiRecorder {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &NotiBoxEvent> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Whether a notification with this message was sent
+    pub fn contains(&self, msg: &str) -> bool {
+        self.0.iter().any(|noti| noti.msg == msg)
+    }
+}
+
+fn record(mut event: MessageReader<NotiBoxEvent>, mut recorder: ResMut<NotiRecorder>) {
+    recorder.0.extend(event.read().cloned());
+}
+
+/// Assertions on the notifications recorded by [`NotiBoxTestPlugin`]
+pub trait NotiAssertExt {
+    fn noti_recorder(&self) -> &NotiRecorder;
+
+    /// Panic if no notification with this message was sent
+    #[track_caller]
+    fn assert_notified(&self, msg: &str) {
+        let recorder = self.noti_recorder();
+        if !recorder.contains(msg) {
+            let sent: Vec<_> = recorder.iter().map(|noti| noti.msg.as_str()).collect();
+            panic!("no notification {msg:?} was sent, got {sent:?}");
+        }
+    }
+
+    /// Panic if a notification with this message was sent
+    #[track_caller]
+    fn assert_not_notified(&self, msg: &str) {
+        assert!(!self.noti_recorder().contains(msg), "notification {msg:?} was sent");
+    }
+
+    /// Panic if not exactly `count` notifications were sent
+    #[track_caller]
+    fn assert_notified_count(&self, count: usize) {
+        let len = self.noti_recorder().len();
+        assert_eq!(len, count, "{len} notifications were sent instead of {count}");
+    }
+}
+
+impl NotiAssertExt for World {
+    fn noti_recorder(&self) -> &NotiRecorder {
+        self.get_resource::<NotiRecorder>()
+            .expect("NotiBoxTestPlugin must be added to record notifications")
+    }
+}
+
+impl NotiAssertExt for App {
+    fn noti_recorder(&self) -> &NotiRecorder {
+        self.world().noti_recorder()
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{NotiBoxEvent, BACKGROUND_COLOR, DEFAULT_ANIMATION_DURATION};
+use bevy::prelude::*;
+
+/// Default look of notifications. Fields of [`NotiBoxEvent`] left at their default value are taken from here.
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NotiBoxTheme {
+    /// Its handle is also used by notifications only changing the font size.
+    /// Set from an asset path with [`NotiBoxPlugin::with_font`](crate::NotiBoxPlugin::with_font).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_font::font"))]
+    pub font: TextFont,
+    pub text_color: Color,
+    pub background_color: Color,
+    /// Image covering the background of notifications, see [`NotiBoxEvent::background_image`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub background_image: Option<ImageNode>,
+    /// Derived from `background_color` if `None`
+    pub border_color: Option<Color>,
+    /// Border width, [`UiRect::DEFAULT`] for no border
+    pub border: UiRect,
+    /// Use [`BorderRadius::MAX`] for pill-shaped notifications
+    pub border_radius: BorderRadius,
+    /// [`GlobalZIndex`] of notifications, high enough to show above the rest of the UI
+    pub z_index: i32,
+    /// Space between the border and the content
+    pub padding: UiRect,
+    /// Space around each notification. Stacked ones are apart by the sum of their top and bottom margins,
+    /// e.g. 20 pixels with `UiRect::all(Val::Px(10.))`.
+    pub margin: UiRect,
+    /// [`Val::Auto`] to fit the text
+    pub width: Val,
+    /// [`Val::Auto`] to fit the text
+    pub height: Val,
+    /// Width past which messages wrap
+    pub max_width: Val,
+    /// Keeps notifications with a very short message from looking cramped
+    pub min_width: Val,
+    pub min_height: Val,
+    /// Height past which a long message overflows. Use [`NotiBoxEvent::max_lines`] to cut it instead.
+    pub max_height: Val,
+    /// Multiplies the pixel sizes of notifications, fonts included, e.g. `1.5` for a TV.
+    /// [`UiScale`] and the scale factor of the window are applied on top, like for the rest of the UI.
+    pub scale: f32,
+    pub linebreak: LineBreak,
+    /// E.g. [`Justify::Center`] for short centered messages. The line height is the one of `font`.
+    pub justify: Justify,
+    /// Horizontal alignment of the content
+    pub align_items: AlignItems,
+    /// Vertical alignment of the content
+    pub justify_content: JustifyContent,
+    /// Fade-in duration in seconds
+    pub fade_in: f32,
+    /// Fade-out duration in seconds
+    pub fade_out: f32,
+    /// How much lighter the background of an interactive notification gets under the cursor. `0.` to disable.
+    pub hover_lighten: f32,
+    /// Border color of an interactive notification under the cursor. Unchanged if `None`.
+    pub hover_border_color: Option<Color>,
+    /// Color of the screen behind modal notifications
+    pub modal_color: Color,
+    /// Played when a notification appears
+    #[cfg(feature = "audio")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub show_sound: Option<Handle<AudioSource>>,
+    /// Played when a notification goes away
+    #[cfg(feature = "audio")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub dismiss_sound: Option<Handle<AudioSource>>,
+}
+
+impl Default for NotiBoxTheme {
+    fn default() -> Self {
+        Self {
+            font: TextFont::default(),
+            text_color: Color::WHITE,
+            background_color: BACKGROUND_COLOR,
+            background_image: None,
+            border_color: None,
+            border: UiRect::all(Val::Px(1.)),
+            border_radius: BorderRadius::all(Val::Px(6.)),
+            z_index: 1000,
+            padding: UiRect::axes(Val::Px(12.), Val::Px(8.)),
+            margin: UiRect::all(Val::Px(5.)),
+            width: Val::Auto,
+            height: Val::Auto,
+            max_width: Val::Px(400.),
+            min_width: Val::Auto,
+            min_height: Val::Auto,
+            max_height: Val::Auto,
+            scale: 1.,
+            linebreak: LineBreak::WordBoundary,
+            justify: Justify::Left,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            fade_in: DEFAULT_ANIMATION_DURATION,
+            fade_out: DEFAULT_ANIMATION_DURATION,
+            hover_lighten: 0.05,
+            hover_border_color: None,
+            modal_color: Color::BLACK.with_alpha(0.5),
+            #[cfg(feature = "audio")]
+            show_sound: None,
+            #[cfg(feature = "audio")]
+            dismiss_sound: None,
+        }
+    }
+}
+
+impl NotiBoxTheme {
+    /// Replace the fields of `noti` that are left at their default value
+    pub(crate) fn apply(&self, noti: &mut NotiBoxEvent) {
+        let default = NotiBoxEvent::default();
+        if noti.font == default.font {
+            noti.font = self.font.clone();
+        } else if noti.font.font == default.font.font {
+            // Only the size or style was changed, keep the font
+            noti.font.font = self.font.font.clone();
+        }
+        for font in noti.spans.iter_mut().filter_map(|span| span.font.as_mut()) {
+            if font.font == default.font.font {
+                font.font = self.font.font.clone();
+            }
+        }
+        if noti.text_color == default.text_color {
+            noti.text_color = self.text_color;
+        }
+        if noti.background_color == default.background_color {
+            noti.background_color = self.background_color.into();
+        }
+        if noti.background_image.is_none() {
+            noti.background_image = self.background_image.clone();
+        }
+        if noti.border_color.is_none() {
+            noti.border_color = self.border_color;
+        }
+        noti.border_radius = noti.border_radius.or(Some(self.border_radius));
+        if let Some(line_height) = noti.line_height {
+            noti.font.line_height = line_height;
+            for font in noti.spans.iter_mut().filter_map(|span| span.font.as_mut()) {
+                font.line_height = line_height;
+            }
+        }
+        if noti.width == default.width {
+            noti.width = self.width;
+        }
+        if noti.height == default.height {
+            noti.height = self.height;
+        }
+        noti.fade_in = noti.fade_in.or(Some(self.fade_in));
+        noti.fade_out = noti.fade_out.or(Some(self.fade_out));
+        if self.scale != 1. {
+            noti.font.font_size *= self.scale;
+            for font in noti.spans.iter_mut().filter_map(|span| span.font.as_mut()) {
+                font.font_size *= self.scale;
+            }
+            noti.width = self.scaled(noti.width);
+            noti.height = self.scaled(noti.height);
+            noti.max_width = Some(self.scaled(noti.max_width.unwrap_or(self.max_width)));
+            noti.min_width = Some(self.scaled(noti.min_width.unwrap_or(self.min_width)));
+            noti.min_height = Some(self.scaled(noti.min_height.unwrap_or(self.min_height)));
+            noti.max_height = Some(self.scaled(noti.max_height.unwrap_or(self.max_height)));
+            noti.border = Some(self.scaled_rect(noti.border.unwrap_or(self.border)));
+            let radius = noti.border_radius.unwrap_or(self.border_radius);
+            noti.border_radius = Some(BorderRadius {
+                top_left: self.scaled(radius.top_left),
+                top_right: self.scaled(radius.top_right),
+                bottom_left: self.scaled(radius.bottom_left),
+                bottom_right: self.scaled(radius.bottom_right),
+            });
+        }
+        #[cfg(feature = "audio")]
+        {
+            noti.show_sound = noti.show_sound.take().or_else(|| self.show_sound.clone());
+            noti.dismiss_sound = noti.dismiss_sound.take().or_else(|| self.dismiss_sound.clone());
+        }
+    }
+
+    /// `val` multiplied by `scale` if in pixels
+    pub(crate) fn scaled(&self, val: Val) -> Val {
+        match val {
+            Val::Px(px) => Val::Px(px * self.scale),
+            val => val,
+        }
+    }
+
+    pub(crate) fn scaled_rect(&self, rect: UiRect) -> UiRect {
+        UiRect {
+            left: self.scaled(rect.left),
+            right: self.scaled(rect.right),
+            top: self.scaled(rect.top),
+            bottom: self.scaled(rect.bottom),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{NotiAnimationTarget, NotiAnimator, NotiBoxState};
+use bevy::{
+    ecs::{change_detection::MaybeLocation, component::Tick},
+    prelude::*,
+};
+use bevy_tweening::{BoxedTweenable, IntoBoxedTweenable, Lens, TotalDuration, Tween};
+use std::{
+    any::TypeId,
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+/// [`NotiAnimator`] playing `bevy_tweening` tweens on the [`Node`], the [`UiTransform`] and the [`NotiAlpha`] of a
+/// notification, e.g. with [`NotiTranslationLens`], [`NotiScaleLens`] and [`NotiAlphaLens`].
+///
+/// The tweens are stretched to the fade-in and fade-out of the notification, so their durations only matter relative
+/// to each other: the longest one ends with the fade. Without exit tweens, the entrance ones are played backward while it leaves.
+#[derive(Default)]
+pub struct NotiTweens {
+    entrance: Mutex<Vec<BoxedTweenable>>,
+    exit: Mutex<Vec<BoxedTweenable>>,
+}
+
+impl NotiTweens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fade in while sliding from `offset` to the resting place, and back out the same way
+    pub fn slide(offset: Val2, ease: EaseFunction) -> Self {
+        Self::new()
+            .with_entrance(Tween::new(
+                ease,
+                Duration::from_secs(1),
+                NotiTranslationLens {
+                    start: offset,
+                    end: Val2::ZERO,
+                },
+            ))
+            .with_entrance(Tween::new(
+                ease,
+                Duration::from_secs(1),
+                NotiAlphaLens { start: 0., end: 1. },
+            ))
+    }
+
+    /// Fade in while growing from `scale` to full size, and back out the same way
+    pub fn pop(scale: f32, ease: EaseFunction) -> Self {
+        Self::new()
+            .with_entrance(Tween::new(
+                ease,
+                Duration::from_secs(1),
+                NotiScaleLens {
+                    start: Vec2::splat(scale),
+                    end: Vec2::ONE,
+                },
+            ))
+            .with_entrance(Tween::new(
+                ease,
+                Duration::from_secs(1),
+                NotiAlphaLens { start: 0., end: 1. },
+            ))
+    }
+
+    /// Play `tween` while the notification enters
+    pub fn with_entrance(mut self, tween: impl IntoBoxedTweenable) -> Self {
+        self.entrance
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tween.into_boxed());
+        self
+    }
+
+    /// Play `tween` while the notification leaves, from its start when it begins to leave
+    pub fn with_exit(mut self, tween: impl IntoBoxedTweenable) -> Self {
+        self.exit
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tween.into_boxed());
+        self
+    }
+}
+
+impl NotiAnimator for NotiTweens {
+    fn animate(&self, state: NotiBoxState, progress: f32, target: &mut NotiAnimationTarget) {
+        let exit = self.exit.lock().unwrap_or_else(PoisonError::into_inner);
+        let (mut tweens, ratio) = if state == NotiBoxState::Leaving && !exit.is_empty() {
+            (exit, 1. - progress)
+        } else {
+            drop(exit);
+            (self.entrance.lock().unwrap_or_else(PoisonError::into_inner), progress)
+        };
+
+        let longest = tweens
+            .iter()
+            .filter_map(|tween| match tween.total_duration() {
+                TotalDuration::Finite(total) => Some(total),
+                TotalDuration::Infinite => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let elapsed = longest.mul_f32(ratio.clamp(0., 1.));
+
+        let mut alpha = NotiAlpha(target.alpha);
+        for tween in tweens.iter_mut() {
+            seek(tween, elapsed);
+            let Some(type_id) = tween.target_type_id() else {
+                continue;
+            };
+            if type_id == TypeId::of::<Node>() {
+                step(tween, target.node, type_id);
+            } else if type_id == TypeId::of::<UiTransform>() {
+                step(tween, target.transform, type_id);
+            } else if type_id == TypeId::of::<NotiAlpha>() {
+                step(tween, &mut alpha, type_id);
+            }
+        }
+        target.alpha = alpha.0;
+    }
+}
+
+fn seek(tween: &mut BoxedTweenable, elapsed: Duration) {
+    let TotalDuration::Finite(total) = tween.total_duration() else {
+        tween.set_elapsed(elapsed);
+        return;
+    };
+    // A completed tween is not applied anymore, so stop right before its end
+    tween.set_elapsed(elapsed.min(total.saturating_sub(Duration::from_nanos(1))));
+}
+
+/// Apply `tween` at its current time to `value`
+fn step<T>(tween: &mut BoxedTweenable, value: &mut T, type_id: TypeId) {
+    let (mut added, mut changed) = (Tick::default(), Tick::default());
+    let mut caller = MaybeLocation::caller();
+    let value = Mut::new(
+        value,
+        &mut added,
+        &mut changed,
+        Tick::default(),
+        Tick::default(),
+        caller.as_mut(),
+    );
+    tween.step(Entity::PLACEHOLDER, Duration::ZERO, value.into(), &type_id, &mut || {});
+}
+
+/// Opacity of a notification and its content, animated by [`NotiAlphaLens`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotiAlpha(pub f32);
+
+/// Fades a notification
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotiAlphaLens {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Lens<NotiAlpha> for NotiAlphaLens {
+    fn lerp(&mut self, mut target: Mut<NotiAlpha>, ratio: f32) {
+        target.0 = self.start.lerp(self.end, ratio);
+    }
+}
+
+/// Moves a notification away from its resting place.
+/// Both ends should use the same unit, otherwise it jumps from `start` to `end` at the end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotiTranslationLens {
+    pub start: Val2,
+    pub end: Val2,
+}
+
+impl Lens<UiTransform> for NotiTranslationLens {
+    fn lerp(&mut self, mut target: Mut<UiTransform>, ratio: f32) {
+        target.translation = Val2 {
+            x: lerp_val(self.start.x, self.end.x, ratio),
+            y: lerp_val(self.start.y, self.end.y, ratio),
+        };
+    }
+}
+
+/// Scales a notification
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotiScaleLens {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl Lens<UiTransform> for NotiScaleLens {
+    fn lerp(&mut self, mut target: Mut<UiTransform>, ratio: f32) {
+        target.scale = self.start.lerp(self.end, ratio);
+    }
+}
+
+fn lerp_val(start: Val, end: Val, ratio: f32) -> Val {
+    match (start, end) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(a.lerp(b, ratio)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(a.lerp(b, ratio)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(a.lerp(b, ratio)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(a.lerp(b, ratio)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(a.lerp(b, ratio)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(a.lerp(b, ratio)),
+        _ if ratio < 1. => start,
+        _ => end,
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    dismiss_noti_box, spawn_noti_box, DismissReason, NotiAnimation, NotiBox, NotiBoxEvent, NotiBoxId, NotiBoxSettings,
+    NotiBoxTheme, NotiClock, NotiHistory, NotiRecord,
+};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Show a notification following an entity on screen, e.g. "Level up!" over a character's head.
+/// It is shown right away, without waiting in the queue.
+#[derive(Message, Clone)]
+pub struct WorldNotiEvent {
+    pub target: Entity,
+    /// Offset from the position of `target`, in world units
+    pub offset: Vec3,
+    /// Camera `target` is seen through. Falls back to the active camera with the highest order.
+    pub camera: Option<Entity>,
+    pub noti: NotiBoxEvent,
+}
+
+impl WorldNotiEvent {
+    pub fn new(target: Entity, msg: impl Into<String>) -> Self {
+        Self {
+            target,
+            offset: Vec3::ZERO,
+            camera: None,
+            noti: NotiBoxEvent::from_message(msg.into()),
+        }
+    }
+}
+
+/// Short text rising from a world position while fading out quickly, e.g. a damage number.
+/// It is shown right away, without a box behind it and without waiting in the queue.
+/// Unlike notifications, it is still shown when [`NotiBoxSettings::enabled`] is `false`.
+#[derive(Message, Clone)]
+pub struct FloatingTextEvent {
+    /// World position the text starts from
+    pub position: Vec3,
+    pub text: String,
+    pub color: Color,
+    pub font: TextFont,
+    /// Seconds until the text is gone
+    pub duration: f32,
+    /// Camera `position` is seen through. Falls back to the active camera with the highest order.
+    pub camera: Option<Entity>,
+}
+
+impl FloatingTextEvent {
+    pub fn new(position: Vec3, text: impl Into<String>) -> Self {
+        Self {
+            position,
+            text: text.into(),
+            color: Color::WHITE,
+            font: TextFont::default(),
+            duration: 1.,
+            camera: None,
+        }
+    }
+
+    fn to_noti(&self) -> NotiBoxEvent {
+        NotiBoxEvent {
+            msg: self.text.clone(),
+            text_color: self.color,
+            font: self.font.clone(),
+            background_color: Color::NONE.into(),
+            border_color: Some(Color::NONE),
+            // Rise and fade out over the whole duration
+            show_time: f32::EPSILON,
+            fade_in: Some(0.),
+            fade_out: Some(self.duration),
+            entrance: NotiAnimation::Rise,
+            interactive: false,
+            ..default()
+        }
+    }
+}
+
+/// Node positioned every frame at the screen position of `target`, holding one notification
+#[derive(Component)]
+pub(crate) struct NotiWorldAnchor {
+    /// Followed entity, the anchor stays at `offset` if `None`
+    target: Option<Entity>,
+    offset: Vec3,
+    camera: Entity,
+}
+
+/// Notification shown in a [`NotiWorldAnchor`], not part of the stacks of [`NotiPosition`](crate::NotiPosition)
+#[derive(Component)]
+pub(crate) struct NotiWorldBox;
+
+/// Active camera with the highest order
+fn default_camera(cameras: &Query<(Entity, &Camera)>) -> Option<Entity> {
+    cameras
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .max_by_key(|(_, camera)| camera.order)
+        .map(|(e, _)| e)
+}
+
+pub(crate) fn listen_world_event(
+    mut commands: Commands,
+    mut event: MessageReader<WorldNotiEvent>,
+    mut floating: MessageReader<FloatingTextEvent>,
+    cameras: Query<(Entity, &Camera)>,
+    settings: Res<NotiBoxSettings>,
+    theme: Res<NotiBoxTheme>,
+    time: NotiClock,
+    mut history: ResMut<NotiHistory>,
+    asset_server: Option<Res<AssetServer>>,
+) {
+    for world_noti in event.read() {
+        if !settings.enabled {
+            if settings.record_disabled {
+                history.push(NotiRecord::new(&world_noti.noti, time.elapsed()));
+            }
+            continue;
+        }
+        let Some(camera) = world_noti.camera.or_else(|| default_camera(&cameras)) else {
+            warn!("Dropped a world notification because there is no camera");
+            continue;
+        };
+        let mut noti = world_noti.noti.clone();
+        history.push(NotiRecord::new(&noti, time.elapsed()));
+        theme.apply(&mut noti);
+        noti.load_font(asset_server.as_deref());
+        let anchor = NotiWorldAnchor {
+            target: Some(world_noti.target),
+            offset: world_noti.offset,
+            camera,
+        };
+        spawn_world_noti(&mut commands, anchor, &noti, &settings, &theme, time.elapsed());
+    }
+
+    for text in floating.read() {
+        let Some(camera) = text.camera.or_else(|| default_camera(&cameras)) else {
+            warn!("Dropped a floating text because there is no camera");
+            continue;
+        };
+        let mut noti = text.to_noti();
+        theme.apply(&mut noti);
+        noti.load_font(asset_server.as_deref());
+        let anchor = NotiWorldAnchor {
+            target: None,
+            offset: text.position,
+            camera,
+        };
+        spawn_world_noti(&mut commands, anchor, &noti, &settings, &theme, time.elapsed());
+    }
+}
+
+fn spawn_world_noti(
+    commands: &mut Commands,
+    anchor: NotiWorldAnchor,
+    noti: &NotiBoxEvent,
+    settings: &NotiBoxSettings,
+    theme: &NotiBoxTheme,
+    now: Duration,
+) {
+    let camera = anchor.camera;
+    let anchor = commands
+        .spawn((
+            anchor,
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            // Center the notification above the anchor point
+            UiTransform::from_translation(Val2::percent(-50., -100.)),
+            UiTargetCamera(camera),
+            Visibility::Hidden,
+        ))
+        .id();
+    let e = spawn_noti_box(commands, None, anchor, noti, settings, theme, now);
+    commands.entity(e).insert(NotiWorldBox);
+}
+
+/// Move anchors to the screen position of their target, and dismiss their notification once the target is gone
+pub(crate) fn follow_world_target(
+    mut commands: Commands,
+    mut anchors: Query<(Entity, &NotiWorldAnchor, &mut Node, &mut Visibility, Option<&Children>)>,
+    targets: Query<&GlobalTransform>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut noti_boxes: Query<(&NotiBoxId, &mut NotiBox), With<NotiWorldBox>>,
+) {
+    for (e, anchor, mut node, mut visibility, children) in anchors.iter_mut() {
+        let Some(children) = children.filter(|children| !children.is_empty()) else {
+            commands.entity(e).despawn();
+            continue;
+        };
+
+        let target = match anchor.target {
+            Some(target) => targets.get(target).map(GlobalTransform::translation),
+            None => Ok(Vec3::ZERO),
+        };
+        let Ok(target) = target else {
+            for child in children.iter() {
+                if let Ok((id, mut noti_box)) = noti_boxes.get_mut(child) {
+                    if !noti_box.is_leaving() {
+                        dismiss_noti_box(&mut commands, child, *id, &mut noti_box, DismissReason::TargetLost);
+                    }
+                }
+            }
+            continue;
+        };
+
+        let viewport_pos = cameras.get(anchor.camera).ok().and_then(|(camera, camera_transform)| {
+            camera.world_to_viewport(camera_transform, target + anchor.offset).ok()
+        });
+        match viewport_pos {
+            Some(pos) => {
+                let (left, top) = (Val::Px(pos.x), Val::Px(pos.y));
+                if node.left != left || node.top != top {
+                    node.left = left;
+                    node.top = top;
+                }
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
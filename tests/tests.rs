@@ -1 +1,909 @@
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_noti_box::*;
+use std::time::Duration;
 
+/// Headless app showing notifications, where each update lasts 100ms
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .add_plugins((NotiBoxPluginAnyState::any(), NotiBoxTestPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app
+}
+
+fn shown(app: &mut App) -> Vec<String> {
+    app.world_mut()
+        .query::<&NotiBox>()
+        .iter(app.world())
+        .map(|noti_box| noti_box.msg().to_string())
+        .collect()
+}
+
+fn update(app: &mut App, times: usize) {
+    for _ in 0..times {
+        app.update();
+    }
+}
+
+#[test]
+fn record_without_ui() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, NotiBoxTestPlugin))
+        .add_systems(Update, |mut commands: Commands| commands.warning("Low health"));
+    app.update();
+
+    app.assert_notified("Low health");
+    app.assert_not_notified("Saved!");
+    let recorder = app.world().resource::<NotiRecorder>();
+    assert_eq!(recorder.iter().next().map(|noti| noti.level), Some(NotiLevel::Warning));
+}
+
+#[test]
+fn show_then_expire() {
+    let mut app = app();
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+    app.assert_notified_count(1);
+
+    // Default show time and fades
+    update(&mut app, 80);
+    assert!(shown(&mut app).is_empty());
+}
+
+#[test]
+fn disabled() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().enabled = false;
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+
+    app.assert_notified("Saved!");
+    assert!(shown(&mut app).is_empty());
+    assert!(app.world().resource::<NotiHistory>().is_empty());
+}
+
+#[test]
+fn invalid_fades() {
+    let mut app = app();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Saved!").show_for(0.5).fade(-1., f32::NAN).build());
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+
+    update(&mut app, 10);
+    assert!(shown(&mut app).is_empty());
+}
+
+#[test]
+fn delay() {
+    let mut app = app();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Later").delay(1.).build());
+    update(&mut app, 5);
+    assert!(shown(&mut app).is_empty());
+
+    update(&mut app, 7);
+    assert_eq!(shown(&mut app), ["Later"]);
+}
+
+#[test]
+fn endless_delay_dropped() {
+    let mut app = app();
+    let id = NotiBoxId::unique();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Never").id(id).delay(f32::INFINITY).build());
+    update(&mut app, 2);
+    assert!(shown(&mut app).is_empty());
+    let dropped: Vec<_> = app
+        .world_mut()
+        .resource_mut::<Messages<NotiBoxDropped>>()
+        .drain()
+        .map(|dropped| dropped.id)
+        .collect();
+    assert_eq!(dropped, [id]);
+}
+
+#[test]
+fn do_not_disturb_summary() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().do_not_disturb = true;
+    for i in 0..5 {
+        app.world_mut().write_message(NotiBoxEvent::info(format!("Item {i}")));
+    }
+    update(&mut app, 2);
+    assert!(shown(&mut app).is_empty());
+
+    app.world_mut().resource_mut::<NotiBoxSettings>().do_not_disturb = false;
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["5 notifications received"]);
+}
+
+#[test]
+fn recurring() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NotiScheduler>()
+        .add(NotiBoxEvent::info("Autosave complete"), 1., Some(2));
+    // Updates happen every 100ms, one more to send the first one
+    update(&mut app, 11);
+    app.assert_notified_count(1);
+
+    update(&mut app, 20);
+    app.assert_notified_count(2);
+}
+
+#[test]
+fn pool_reuses_entities() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().pool_size = 1;
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Kill").show_for(0.5).build());
+    update(&mut app, 2);
+    let first = app
+        .world_mut()
+        .query_filtered::<Entity, With<NotiBox>>()
+        .single(app.world());
+
+    update(&mut app, 20);
+    assert!(shown(&mut app).is_empty());
+    app.world_mut().write_message(NotiBoxEvent::info("Double kill"));
+    update(&mut app, 2);
+    let second = app
+        .world_mut()
+        .query_filtered::<Entity, With<NotiBox>>()
+        .single(app.world());
+    assert_eq!(first.unwrap(), second.unwrap());
+    assert_eq!(shown(&mut app), ["Double kill"]);
+}
+
+fn hidden(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<&Node, (Without<NotiBox>, Without<ChildOf>)>()
+        .iter(app.world())
+        .filter(|node| node.display == Display::None)
+        .count()
+}
+
+#[test]
+fn pool_refills() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().pool_size = 2;
+    update(&mut app, 1);
+    assert_eq!(hidden(&mut app), 2);
+
+    app.world_mut().write_message(NotiBoxEvent::info("Kill"));
+    update(&mut app, 2);
+    assert_eq!(hidden(&mut app), 1);
+
+    // Gone with its parent instead of going back to the pool
+    let e = app
+        .world_mut()
+        .query_filtered::<Entity, With<NotiBox>>()
+        .single(app.world())
+        .unwrap();
+    app.world_mut().despawn(e);
+    update(&mut app, 1);
+    assert_eq!(hidden(&mut app), 2);
+}
+
+#[derive(Resource, Default)]
+struct Dismissed(Vec<DismissReason>);
+
+/// Record the reason of each [`NotiBoxDismissed`] in [`Dismissed`]
+fn record_dismissals(app: &mut App) {
+    app.init_resource::<Dismissed>().add_systems(
+        Update,
+        |mut dismissed: MessageReader<NotiBoxDismissed>, mut reasons: ResMut<Dismissed>| {
+            reasons.0.extend(dismissed.read().map(|dismissed| dismissed.reason));
+        },
+    );
+}
+
+#[test]
+fn dismiss_twice_in_one_frame() {
+    let mut app = app();
+    record_dismissals(&mut app);
+    app.world_mut().resource_mut::<NotiBoxSettings>().pool_size = 1;
+    let id = NotiBoxId::unique();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Kill").id(id).fade(0.1, 0.).build());
+    update(&mut app, 2);
+
+    press(&mut app, "Kill");
+    app.world_mut().write_message(DismissNoti(id));
+    update(&mut app, 1);
+    assert!(shown(&mut app).is_empty());
+    // Read the next frame
+    update(&mut app, 1);
+    assert_eq!(app.world().resource::<Dismissed>().0.len(), 1);
+    assert_eq!(hidden(&mut app), 1);
+}
+
+#[test]
+fn observers() {
+    let mut app = app();
+    app.init_resource::<Dismissed>();
+    app.world_mut().commands().trigger(
+        NotiBoxEvent {
+            show_time: 0.5,
+            ..NotiBoxEvent::info("Quest updated")
+        }
+        .with(|entity| {
+            entity.observe(|dismissed: On<OnNotiDismissed>, mut reasons: ResMut<Dismissed>| {
+                reasons.0.push(dismissed.reason);
+            });
+        }),
+    );
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Quest updated"]);
+
+    update(&mut app, 20);
+    assert!(shown(&mut app).is_empty());
+    assert_eq!(app.world().resource::<Dismissed>().0, [DismissReason::Timeout]);
+}
+
+#[test]
+fn send_from_thread() {
+    let mut app = app();
+    let sender = app.world().resource::<NotiSender>().clone();
+    std::thread::spawn(move || sender.send(NotiBoxEvent::success("Download complete")))
+        .join()
+        .unwrap();
+    update(&mut app, 2);
+    app.assert_notified("Download complete");
+    assert_eq!(shown(&mut app), ["Download complete"]);
+}
+
+#[derive(States, Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+enum GameState {
+    #[default]
+    Playing,
+    Menu,
+}
+
+fn state_app(policy: NotiStatePolicy) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .init_state::<GameState>()
+        .add_plugins(NotiBoxPlugin::new(vec![GameState::Playing]).with_state_policy(policy))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<Dismissed>()
+        .add_observer(|dismissed: On<OnNotiDismissed>, mut reasons: ResMut<Dismissed>| {
+            reasons.0.push(dismissed.reason);
+        });
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Menu);
+    update(&mut app, 2);
+    app
+}
+
+#[test]
+fn despawn_on_state_exit() {
+    let mut app = state_app(NotiStatePolicy::Despawn);
+    assert!(shown(&mut app).is_empty());
+    assert_eq!(app.world().resource::<Dismissed>().0, [DismissReason::StateExit]);
+}
+
+#[test]
+fn keep_on_state_exit_by_default() {
+    let mut app = state_app(NotiStatePolicy::default());
+    assert_eq!(shown(&mut app), ["Saved!"]);
+    assert!(app.world().resource::<Dismissed>().0.is_empty());
+}
+
+#[test]
+fn pause_on_state_exit() {
+    let mut app = state_app(NotiStatePolicy::Pause);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+    // Visibility of the containers holding the notifications
+    let container_visibility = |app: &mut App| {
+        let parents: Vec<Entity> = app
+            .world_mut()
+            .query_filtered::<&ChildOf, With<NotiBox>>()
+            .iter(app.world())
+            .map(ChildOf::parent)
+            .collect();
+        parents
+            .into_iter()
+            .map(|parent| *app.world().get::<Visibility>(parent).unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(container_visibility(&mut app), [Visibility::Hidden]);
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    update(&mut app, 2);
+    assert_eq!(container_visibility(&mut app), [Visibility::Inherited]);
+}
+
+#[test]
+fn position_limits() {
+    for (overflow, expected) in [
+        (NotiOverflow::DropOldest, vec!["b"]),
+        (NotiOverflow::DropNewest, vec!["a"]),
+        (NotiOverflow::Queue, vec!["a"]),
+    ] {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<NotiBoxSettings>()
+            .position_limits
+            .insert(NotiPosition::TopRight, NotiLimit::new(1, overflow));
+        app.world_mut().write_message(NotiBoxEvent::info("a"));
+        update(&mut app, 2);
+        app.world_mut().write_message(NotiBoxEvent::info("b"));
+        // Long enough for "a" to fade out when evicted
+        update(&mut app, 10);
+
+        let mut shown = shown(&mut app);
+        shown.sort();
+        assert_eq!(shown, expected, "{overflow:?}");
+    }
+}
+
+#[test]
+fn position_limit_burst() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NotiBoxSettings>()
+        .position_limits
+        .insert(NotiPosition::TopRight, NotiLimit::new(2, NotiOverflow::DropOldest));
+    for msg in ["a", "b", "c", "d"] {
+        app.world_mut().write_message(NotiBoxEvent::info(msg));
+    }
+    update(&mut app, 2);
+
+    let mut shown = shown(&mut app);
+    shown.sort();
+    assert_eq!(shown, ["c", "d"]);
+}
+
+#[test]
+fn typewriter() {
+    let mut app = app();
+    app.world_mut().write_message(NotiBoxEvent {
+        typewriter: 10.,
+        ..NotiBoxEvent::info("Hello world")
+    });
+    let typed = |app: &mut App| {
+        app.world_mut()
+            .query::<&Text>()
+            .iter(app.world())
+            .map(|text| text.0.clone())
+            .find(|text| "Hello world".starts_with(text.as_str()))
+    };
+    update(&mut app, 5);
+    let partial = typed(&mut app).unwrap();
+    assert!(!partial.is_empty() && partial.len() < "Hello world".len(), "{partial}");
+
+    update(&mut app, 10);
+    assert_eq!(typed(&mut app).as_deref(), Some("Hello world"));
+}
+
+#[test]
+fn queue_beyond_max_visible() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().max_visible = 1;
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("First").show_for(1.).build());
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Second").show_for(1.).build());
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["First"]);
+
+    update(&mut app, 30);
+    assert_eq!(shown(&mut app), ["Second"]);
+}
+
+#[test]
+fn deduplicate() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().deduplicate = true;
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+
+    assert_eq!(shown(&mut app), ["Saved!"]);
+    let repeats: Vec<_> = app
+        .world_mut()
+        .query::<&NotiBox>()
+        .iter(app.world())
+        .map(NotiBox::repeat_count)
+        .collect();
+    assert_eq!(repeats, [3]);
+}
+
+#[test]
+fn deduplicate_only_same_text() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().deduplicate = true;
+    app.world_mut().write_message(NotiBoxEvent::info("Found"));
+    update(&mut app, 2);
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Found").span(NotiTextSpan::new(" a sword")).build());
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Found").id(NotiBoxId::unique()).build());
+    update(&mut app, 2);
+
+    assert_eq!(shown(&mut app).len(), 3);
+}
+
+#[test]
+fn high_priority_jumps_queue() {
+    let mut app = app();
+    record_dismissals(&mut app);
+    app.world_mut().resource_mut::<NotiBoxSettings>().max_visible = 1;
+    app.world_mut().write_message(NotiBoxEvent::info("First"));
+    update(&mut app, 2);
+    app.world_mut().write_message(NotiBoxEvent::info("Queued"));
+    app.world_mut().write_message(NotiBoxEvent {
+        priority: NotiPriority::High,
+        show_time: 0.5,
+        ..NotiBoxEvent::info("Urgent")
+    });
+    update(&mut app, 2);
+
+    // The replaced one is fading out
+    assert!(shown(&mut app).contains(&"Urgent".to_string()));
+    assert!(!shown(&mut app).contains(&"Queued".to_string()));
+    update(&mut app, 10);
+    assert_eq!(app.world().resource::<Dismissed>().0, [DismissReason::Evicted]);
+
+    // The displaced one waits for a free slot
+    update(&mut app, 20);
+    assert_eq!(shown(&mut app), ["Queued"]);
+}
+
+#[test]
+fn rate_limit() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().max_per_second = 2;
+    for i in 0..5 {
+        app.world_mut().write_message(NotiBoxEvent::info(format!("Item {i}")));
+    }
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app).len(), 2);
+}
+
+#[test]
+fn repeat_interval() {
+    let mut app = app();
+    let mut settings = app.world_mut().resource_mut::<NotiBoxSettings>();
+    settings.repeat_interval = 1.;
+    settings.deduplicate = false;
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+
+    update(&mut app, 10);
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!", "Saved!"]);
+}
+
+#[test]
+fn infinite_repeat_interval() {
+    let mut app = app();
+    let mut settings = app.world_mut().resource_mut::<NotiBoxSettings>();
+    settings.repeat_interval = f32::INFINITY;
+    settings.deduplicate = false;
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+}
+
+#[cfg(feature = "template")]
+#[test]
+fn template_without_asset_plugin() {
+    let mut app = app();
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+}
+
+/// Notification showing `msg`
+fn find(app: &mut App, msg: &str) -> Entity {
+    app.world_mut()
+        .query::<(Entity, &NotiBox)>()
+        .iter(app.world())
+        .find(|(_, noti_box)| noti_box.msg() == msg)
+        .map(|(e, _)| e)
+        .unwrap()
+}
+
+/// Press the notification showing `msg`, like a click would
+#[cfg(not(feature = "picking"))]
+fn press(app: &mut App, msg: &str) {
+    let e = find(app, msg);
+    app.world_mut().entity_mut(e).insert(Interaction::Pressed);
+}
+
+/// Send a `bevy_picking` pointer `event` to the notification showing `msg`
+#[cfg(feature = "picking")]
+fn pointer<E: std::fmt::Debug + Clone + Reflect>(app: &mut App, msg: &str, event: E) {
+    use bevy::{
+        camera::NormalizedRenderTarget,
+        picking::pointer::{Location, PointerId},
+    };
+
+    let e = find(app, msg);
+    let location = Location {
+        target: NormalizedRenderTarget::None { width: 1, height: 1 },
+        position: Vec2::ZERO,
+    };
+    app.world_mut()
+        .trigger(Pointer::new(PointerId::Mouse, location, event, e));
+}
+
+#[cfg(feature = "picking")]
+fn press(app: &mut App, msg: &str) {
+    use bevy::picking::backend::HitData;
+
+    let click = Click {
+        button: PointerButton::Primary,
+        hit: HitData::new(Entity::PLACEHOLDER, 0., None, None),
+        duration: Duration::ZERO,
+    };
+    pointer(app, msg, click);
+}
+
+#[test]
+fn click_dismisses_message_fitting_max_lines() {
+    let mut app = app();
+    app.world_mut().write_message(NotiBoxEvent {
+        max_lines: Some(2),
+        ..NotiBoxEvent::info("Saved!")
+    });
+    update(&mut app, 2);
+    press(&mut app, "Saved!");
+    update(&mut app, 1);
+    assert!(shown(&mut app).is_empty());
+}
+
+#[test]
+fn channel_limit() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NotiChannels>()
+        .insert("loot", NotiChannel::default().with_max_visible(1));
+    app.world_mut().write_message(NotiBoxEvent::info("Level up"));
+    for msg in ["Sword", "Shield"] {
+        app.world_mut()
+            .write_message(NotiBoxBuilder::msg(msg).channel("loot").build());
+        update(&mut app, 2);
+    }
+    // Long enough for the replaced one to fade out
+    update(&mut app, 10);
+
+    let mut shown = shown(&mut app);
+    shown.sort();
+    assert_eq!(shown, ["Level up", "Shield"]);
+}
+
+#[test]
+fn channel_limit_burst() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiChannels>().insert(
+        "loot",
+        NotiChannel::default()
+            .with_max_visible(1)
+            .with_overflow(NotiOverflow::DropOldest),
+    );
+    for msg in ["Sword", "Shield", "Potion"] {
+        app.world_mut()
+            .write_message(NotiBoxBuilder::msg(msg).channel("loot").build());
+    }
+    app.world_mut().write_message(NotiBoxEvent::info("Level up"));
+    update(&mut app, 2);
+
+    let mut shown = shown(&mut app);
+    shown.sort();
+    assert_eq!(shown, ["Level up", "Potion"]);
+}
+
+#[test]
+fn mute_channel() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiChannels>().mute("loot");
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Found a sword").channel("loot").build());
+    update(&mut app, 2);
+    assert!(shown(&mut app).is_empty());
+
+    app.world_mut().resource_mut::<NotiChannels>().unmute("loot");
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Found a shield").channel("loot").build());
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Found a shield"]);
+}
+
+#[cfg(feature = "tweening")]
+#[test]
+fn tweens_slide_in() {
+    let mut app = app();
+    app.world_mut().write_message(
+        NotiBoxBuilder::msg("Saved!")
+            .fade(1., 1.)
+            .animator(NotiTweens::slide(Val2::px(-100., 0.), EaseFunction::Linear))
+            .build(),
+    );
+    let mut translation = |times| {
+        update(&mut app, times);
+        app.world_mut()
+            .query_filtered::<&UiTransform, With<NotiBox>>()
+            .single(app.world())
+            .unwrap()
+            .translation
+            .x
+    };
+
+    let Val::Px(x) = translation(5) else { panic!() };
+    assert!(-100. < x && x < 0.);
+    let Val::Px(x) = translation(10) else { panic!() };
+    assert!(x.abs() < 1.);
+}
+
+#[derive(Resource, Default)]
+struct Cancelled(Vec<String>);
+
+fn confirm_app() -> App {
+    let mut app = app();
+    app.init_resource::<Cancelled>().add_systems(
+        Update,
+        |mut cancelled: MessageReader<NotiCancelled>, mut payloads: ResMut<Cancelled>| {
+            payloads
+                .0
+                .extend(cancelled.read().map(|cancelled| cancelled.payload.clone()));
+        },
+    );
+    app
+}
+
+#[test]
+fn cancel_confirm_never_shown() {
+    let mut app = confirm_app();
+    app.world_mut().resource_mut::<NotiChannels>().mute("dialogs");
+    let mut confirm = NotiConfirmEvent::new("Delete save?", "slot 1");
+    confirm.noti.channel = Some("dialogs".to_string());
+    app.world_mut().write_message(confirm);
+    update(&mut app, 3);
+    assert_eq!(app.world().resource::<Cancelled>().0, ["slot 1"]);
+}
+
+#[test]
+fn duplicate_confirms_both_shown() {
+    let mut app = confirm_app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().deduplicate = true;
+    app.world_mut()
+        .write_message(NotiConfirmEvent::new("Delete save?", "slot 1"));
+    app.world_mut()
+        .write_message(NotiConfirmEvent::new("Delete save?", "slot 2"));
+    update(&mut app, 3);
+    assert_eq!(shown(&mut app), ["Delete save?", "Delete save?"]);
+    assert!(app.world().resource::<Cancelled>().0.is_empty());
+}
+
+#[test]
+fn sequence_skips_dropped_step() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiChannels>().mute("hints");
+    app.world_mut().write_message(NotiSequenceEvent::new([
+        NotiBoxBuilder::msg("Move with WASD").show_for(0.5).build(),
+        NotiBoxBuilder::msg("Press F for hints").channel("hints").build(),
+        NotiBoxBuilder::msg("Jump with Space").build(),
+    ]));
+    update(&mut app, 2);
+    assert_eq!(shown(&mut app), ["Move with WASD"]);
+
+    update(&mut app, 20);
+    assert_eq!(shown(&mut app), ["Jump with Space"]);
+}
+
+#[cfg(feature = "picking")]
+#[test]
+fn pointer_over_pauses() {
+    use bevy::picking::backend::HitData;
+
+    let mut app = app();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Saved!").show_for(0.5).interactive(true).build());
+    update(&mut app, 2);
+    let hit = HitData::new(Entity::PLACEHOLDER, 0., None, None);
+    pointer(&mut app, "Saved!", Over { hit: hit.clone() });
+    update(&mut app, 30);
+    assert_eq!(shown(&mut app), ["Saved!"]);
+
+    pointer(&mut app, "Saved!", Out { hit });
+    update(&mut app, 30);
+    assert!(shown(&mut app).is_empty());
+}
+
+#[cfg(feature = "picking")]
+#[test]
+fn pointer_goes_through_non_interactive() {
+    use bevy::picking::Pickable;
+
+    let mut app = app();
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Saved!").interactive(false).build());
+    update(&mut app, 2);
+    let e = find(&mut app, "Saved!");
+    let children = app.world().get::<Children>(e).unwrap().to_vec();
+    for e in std::iter::once(e).chain(children) {
+        assert_eq!(app.world().get::<Pickable>(e), Some(&Pickable::IGNORE));
+    }
+}
+
+#[cfg(feature = "picking")]
+#[test]
+fn pointer_ignored_while_systems_off() {
+    #[derive(Resource)]
+    struct Paused;
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .add_plugins(NotiBoxPluginAnyState::run_if(not(resource_exists::<Paused>)));
+    app.world_mut().write_message(NotiBoxEvent::info("Saved!"));
+    update(&mut app, 2);
+    app.world_mut().insert_resource(Paused);
+    update(&mut app, 1);
+    press(&mut app, "Saved!");
+
+    let noti_box = app.world_mut().query::<&NotiBox>().single(app.world()).unwrap();
+    assert_eq!(noti_box.dismiss_reason(), None);
+}
+
+#[derive(Resource, Default)]
+struct Native(Vec<String>);
+
+fn unfocused_app() -> App {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxSettings>().native_when_unfocused = true;
+    app.world_mut().spawn((
+        Window {
+            focused: false,
+            ..default()
+        },
+        bevy::window::PrimaryWindow,
+    ));
+    app.init_resource::<Native>().add_systems(
+        Update,
+        |mut requests: MessageReader<NotiNativeRequest>, mut native: ResMut<Native>| {
+            native.0.extend(requests.read().map(|request| request.msg.clone()));
+        },
+    );
+    app
+}
+
+#[test]
+fn native_when_unfocused() {
+    let mut app = unfocused_app();
+    app.world_mut().write_message(NotiBoxEvent::info("Your turn"));
+    update(&mut app, 3);
+    assert!(shown(&mut app).is_empty());
+    assert_eq!(app.world().resource::<Native>().0, ["Your turn"]);
+}
+
+#[test]
+fn native_after_filters() {
+    let mut app = unfocused_app();
+    app.world_mut().resource_mut::<NotiChannels>().mute("chat");
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Hi").channel("chat").build());
+    update(&mut app, 3);
+
+    app.world_mut().resource_mut::<NotiBoxSettings>().enabled = false;
+    app.world_mut().write_message(NotiBoxEvent::info("Your turn"));
+    update(&mut app, 3);
+    assert!(app.world().resource::<Native>().0.is_empty());
+}
+
+#[test]
+fn occluded_during_do_not_disturb() {
+    let mut app = unfocused_app();
+    let window = app
+        .world_mut()
+        .query_filtered::<(Entity, &mut Window), With<bevy::window::PrimaryWindow>>()
+        .single_mut(app.world_mut())
+        .map(|(e, mut window)| {
+            window.focused = true;
+            e
+        })
+        .unwrap();
+    app.world_mut().resource_mut::<NotiBoxSettings>().do_not_disturb = true;
+    app.world_mut()
+        .write_message(bevy::window::WindowOccluded { window, occluded: true });
+    update(&mut app, 2);
+
+    app.world_mut().resource_mut::<NotiBoxSettings>().do_not_disturb = false;
+    app.world_mut().write_message(NotiBoxEvent::info("Your turn"));
+    update(&mut app, 3);
+    assert_eq!(app.world().resource::<Native>().0, ["Your turn"]);
+}
+
+/// Layout of the stack holding the only notification
+fn container(app: &mut App) -> Node {
+    let parent = app
+        .world_mut()
+        .query_filtered::<&ChildOf, With<NotiBox>>()
+        .single(app.world())
+        .unwrap()
+        .parent();
+    app.world().get::<Node>(parent).unwrap().clone()
+}
+
+#[test]
+fn offset_inside_safe_area() {
+    let mut app = app();
+    let mut settings = app.world_mut().resource_mut::<NotiBoxSettings>();
+    settings.safe_area = UiRect::top(Val::Px(40.));
+    settings
+        .offsets
+        .insert(NotiPosition::TopRight, UiRect::top(Val::Px(10.)));
+    app.world_mut().write_message(NotiBoxBuilder::msg("Saved!").build());
+    update(&mut app, 2);
+
+    let node = container(&mut app);
+    assert_eq!(node.top, Val::Px(40.));
+    assert_eq!(node.padding.top, Val::Px(10.));
+}
+
+#[test]
+fn settings_change_keeps_offset() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NotiBoxSettings>()
+        .offsets
+        .insert(NotiPosition::TopRight, UiRect::top(Val::Px(10.)));
+    app.world_mut().write_message(NotiBoxBuilder::msg("Saved!").build());
+    update(&mut app, 2);
+
+    app.world_mut()
+        .resource_mut::<NotiBoxSettings>()
+        .stack_orders
+        .insert(NotiPosition::TopRight, NotiStackOrder::NewestAbove);
+    update(&mut app, 1);
+    let node = container(&mut app);
+    assert_eq!(node.flex_direction, FlexDirection::ColumnReverse);
+    assert_eq!(node.padding.top, Val::Px(10.));
+}
+
+#[test]
+fn offset_only_at_its_position() {
+    let mut app = app();
+    app.world_mut()
+        .resource_mut::<NotiBoxSettings>()
+        .offsets
+        .insert(NotiPosition::TopRight, UiRect::top(Val::Px(10.)));
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Saved!").pos(NotiPosition::BotLeft).build());
+    update(&mut app, 2);
+
+    assert_eq!(container(&mut app).padding, UiRect::default());
+}
+
+#[test]
+fn icon_follows_theme_scale() {
+    let mut app = app();
+    app.world_mut().resource_mut::<NotiBoxTheme>().scale = 2.;
+    app.world_mut()
+        .write_message(NotiBoxBuilder::msg("Saved!").icon(Handle::default()).build());
+    update(&mut app, 2);
+
+    let icon = app
+        .world_mut()
+        .query_filtered::<&Node, With<ImageNode>>()
+        .single(app.world())
+        .unwrap();
+    assert_eq!((icon.width, icon.margin.right), (Val::Px(64.), Val::Px(10.)));
+}